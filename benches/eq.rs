@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use inline_str::InlineStr;
+
+fn long_string_eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq");
+
+    for len in [16, 256, 4096] {
+        let text = "a".repeat(len);
+        let a = InlineStr::from(&text);
+        let b = InlineStr::from(&text);
+        let shared = a.clone();
+
+        // The comparison `#[derive(PartialEq)]` used to do, kept here as the baseline.
+        group.bench_with_input(BenchmarkId::new("slice", len), &len, |bench, _| {
+            bench.iter(|| black_box(&a).as_bytes() == black_box(&b).as_bytes())
+        });
+        group.bench_with_input(BenchmarkId::new("slice_shared", len), &len, |bench, _| {
+            bench.iter(|| black_box(&a).as_bytes() == black_box(&shared).as_bytes())
+        });
+        group.bench_with_input(BenchmarkId::new("inline_str", len), &len, |bench, _| {
+            bench.iter(|| black_box(&a) == black_box(&b))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("inline_str_shared", len),
+            &len,
+            |bench, _| bench.iter(|| black_box(&a) == black_box(&shared)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, long_string_eq);
+criterion_main!(benches);
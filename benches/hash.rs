@@ -0,0 +1,41 @@
+use std::hash::{BuildHasher, Hasher, RandomState};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use inline_str::InlineStr;
+
+/// Hashes the raw bytes plus the terminator `str::hash` writes, the alternative to delegating to it.
+fn hash_bytes<S: BuildHasher>(build_hasher: &S, value: &InlineStr) -> u64 {
+    let mut hasher = build_hasher.build_hasher();
+    hasher.write(value.as_bytes());
+    hasher.write_u8(0xff);
+    hasher.finish()
+}
+
+fn hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    let build_hasher = RandomState::new();
+
+    for len in [4, 64, 1024] {
+        let text = "a".repeat(len);
+        let value = InlineStr::from(&text);
+        assert_eq!(
+            build_hasher.hash_one(&value),
+            hash_bytes(&build_hasher, &value)
+        );
+
+        group.bench_with_input(BenchmarkId::new("str", len), &len, |bench, _| {
+            bench.iter(|| build_hasher.hash_one(black_box(text.as_str())))
+        });
+        group.bench_with_input(BenchmarkId::new("inline_str", len), &len, |bench, _| {
+            bench.iter(|| build_hasher.hash_one(black_box(&value)))
+        });
+        group.bench_with_input(BenchmarkId::new("bytes", len), &len, |bench, _| {
+            bench.iter(|| hash_bytes(&build_hasher, black_box(&value)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, hash);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use inline_str::InlineStr;
+
+/// Run with `--features simdutf8` to compare against the default `core::str::from_utf8` path.
+fn validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("utf8");
+
+    for (name, unit) in [("ascii", "abcdefgh"), ("mixed", "héllo wörld €😀 ")] {
+        let text = unit.repeat(1024 / unit.len());
+        let bytes = text.as_bytes();
+
+        group.bench_with_input(BenchmarkId::new("std", name), bytes, |bench, bytes| {
+            bench.iter(|| InlineStr::from(std::str::from_utf8(black_box(bytes)).unwrap()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("inline_str", name),
+            bytes,
+            |bench, bytes| bench.iter(|| InlineStr::try_from(black_box(bytes)).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, validate);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::InlineStr;
+
+impl<'a> Arbitrary<'a> for InlineStr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&str>::arbitrary(u).map(InlineStr::from)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        <&str>::arbitrary_take_rest(u).map(InlineStr::from)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::XorShift;
+
+    #[test]
+    fn test_arbitrary_valid_utf8() {
+        let mut rng = XorShift::new(0xa5b1);
+        let (mut saw_empty, mut saw_long, mut saw_multibyte) = (false, false, false);
+
+        for _ in 0..500 {
+            let mut data = rng.string(20).into_bytes();
+            data.push(rng.next() as u8);
+
+            let mut u = Unstructured::new(&data);
+            let s = InlineStr::arbitrary(&mut u).unwrap();
+
+            assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+            assert_eq!(InlineStr::from(s.as_str()), s);
+
+            saw_empty |= s.is_empty();
+            saw_long |= s.len() > 16;
+            saw_multibyte |= s.len() != s.chars().count();
+        }
+
+        assert!(saw_empty && saw_long && saw_multibyte);
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arrow_array::{builder::StringBuilder, StringArray};
+
+use crate::InlineStr;
+
+/// Builds an Arrow [`StringArray`] out of [`InlineStr`]s.
+pub fn collect_string_array<I: IntoIterator<Item = InlineStr>>(iter: I) -> StringArray {
+    let iter = iter.into_iter();
+    let mut builder = StringBuilder::with_capacity(iter.size_hint().0, 0);
+
+    for value in iter {
+        builder.append_value(value.as_str());
+    }
+
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::Array;
+
+    use super::*;
+
+    #[test]
+    fn test_collect_string_array() {
+        let array = collect_string_array([
+            InlineStr::from("key"),
+            InlineStr::from("a much longer string that is stored on the heap"),
+        ]);
+
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.null_count(), 0);
+        assert_eq!(array.value(0), "key");
+        assert_eq!(
+            array.value(1),
+            "a much longer string that is stored on the heap"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `InlineStr` uses the same wire format as `String`: a length prefix followed by the UTF-8 bytes.
+
+use alloc::vec;
+
+use bincode::{
+    de::{read::Reader, BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+
+use crate::InlineStr;
+
+impl Encode for InlineStr {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_str().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for InlineStr {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = u64::decode(decoder)?;
+        let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+        decoder.claim_container_read::<u8>(len)?;
+
+        // Short strings are read straight into a stack buffer, skipping the intermediate `Vec`
+        if len <= InlineStr::INLINE_CAPACITY {
+            let mut buf = [0; InlineStr::INLINE_CAPACITY];
+            decoder.reader().read(&mut buf[..len])?;
+            return from_utf8(&buf[..len]);
+        }
+
+        let mut buf = vec![0; len];
+        decoder.reader().read(&mut buf)?;
+        from_utf8(&buf)
+    }
+}
+
+impl<'de, Context> BorrowDecode<'de, Context> for InlineStr {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        <&str>::borrow_decode(decoder).map(InlineStr::from)
+    }
+}
+
+fn from_utf8(bytes: &[u8]) -> Result<InlineStr, DecodeError> {
+    crate::from_utf8(bytes)
+        .map(InlineStr::from)
+        .map_err(|inner| DecodeError::Utf8 { inner })
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+
+    use super::*;
+
+    #[test]
+    fn test_bincode_roundtrip() {
+        for text in [
+            "",
+            "short",
+            "日本語",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = InlineStr::from(text);
+
+            let bytes = bincode::encode_to_vec(&value, config::standard()).unwrap();
+            assert_eq!(
+                bytes,
+                bincode::encode_to_vec(text, config::standard()).unwrap()
+            );
+
+            let (decoded, read): (InlineStr, usize) =
+                bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(read, bytes.len());
+
+            let (borrowed, _): (InlineStr, usize) =
+                bincode::borrow_decode_from_slice(&bytes, config::standard()).unwrap();
+            assert_eq!(borrowed, value);
+        }
+    }
+
+    #[test]
+    fn test_bincode_invalid_utf8() {
+        for bytes in [&[0xff, 0xfe][..], &[b'a'; 20][..]] {
+            let mut bytes = bincode::encode_to_vec(bytes, config::standard()).unwrap();
+            *bytes.last_mut().unwrap() = 0xff;
+
+            let err =
+                bincode::decode_from_slice::<InlineStr, _>(&bytes, config::standard()).unwrap_err();
+            assert!(matches!(err, DecodeError::Utf8 { .. }));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `InlineStr` uses the same wire format as `String`: a `u32` length prefix followed by the UTF-8 bytes.
+
+use alloc::{string::ToString, vec::Vec};
+
+use borsh::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+
+use crate::InlineStr;
+
+impl BorshSerialize for InlineStr {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.as_str().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for InlineStr {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        crate::from_utf8(&bytes)
+            .map(InlineStr::from)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borsh_matches_string() {
+        for text in [
+            "",
+            "short",
+            "日本語",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = InlineStr::from(text);
+
+            let bytes = borsh::to_vec(&value).unwrap();
+            assert_eq!(bytes, borsh::to_vec(&text.to_string()).unwrap());
+
+            assert_eq!(borsh::from_slice::<InlineStr>(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_borsh_invalid_utf8() {
+        let bytes = borsh::to_vec(&vec![b'a', 0xff]).unwrap();
+        let err = borsh::from_slice::<InlineStr>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
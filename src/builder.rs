@@ -0,0 +1,117 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use core::str;
+
+use crate::InlineStr;
+
+/// Accumulates a string on the stack, only spilling to a `String` once it outgrows the
+/// inline capacity, so short results never touch the allocator.
+pub(crate) enum Builder {
+    Inline {
+        buf: [u8; InlineStr::INLINE_CAPACITY],
+        len: usize,
+    },
+    Heap(String),
+}
+
+impl Builder {
+    pub(crate) fn new() -> Self {
+        Self::Inline {
+            buf: [0; InlineStr::INLINE_CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Creates a builder for a string of `capacity` bytes, allocating it upfront if it won't fit inline.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        if capacity <= InlineStr::INLINE_CAPACITY {
+            Self::new()
+        } else {
+            Self::Heap(String::with_capacity(capacity))
+        }
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        match self {
+            Self::Inline { buf, len } if *len + s.len() <= InlineStr::INLINE_CAPACITY => {
+                buf[*len..*len + s.len()].copy_from_slice(s.as_bytes());
+                *len += s.len();
+            }
+            Self::Inline { buf, len } => {
+                let mut heap =
+                    String::with_capacity((*len + s.len()).max(2 * InlineStr::INLINE_CAPACITY));
+                // Safety:
+                // The buffer only ever receives whole `&str`s
+                heap.push_str(unsafe { str::from_utf8_unchecked(&buf[..*len]) });
+                heap.push_str(s);
+                *self = Self::Heap(heap);
+            }
+            Self::Heap(heap) => heap.push_str(s),
+        }
+    }
+
+    pub(crate) fn finish(self) -> InlineStr {
+        match self {
+            Self::Inline { buf, len } => {
+                // Safety:
+                // The buffer only ever receives whole `&str`s
+                InlineStr::from(unsafe { str::from_utf8_unchecked(&buf[..len]) })
+            }
+            Self::Heap(heap) => InlineStr::from(heap),
+        }
+    }
+}
+
+impl core::fmt::Write for Builder {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_spills() {
+        let mut builder = Builder::new();
+        builder.push_str("abc");
+        builder.push('é');
+        assert!(matches!(builder, Builder::Inline { len: 5, .. }));
+
+        builder.push('😀');
+        assert!(matches!(builder, Builder::Heap(_)));
+        builder.push_str("xyz");
+
+        assert_eq!(builder.finish(), "abcé😀xyz");
+        assert_eq!(Builder::new().finish(), "");
+
+        let mut builder = Builder::with_capacity(10);
+        builder.push_str("ab");
+        assert!(matches!(&builder, Builder::Heap(heap) if heap.capacity() >= 10));
+        assert_eq!(builder.finish(), "ab");
+    }
+}
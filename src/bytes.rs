@@ -0,0 +1,64 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `InlineArray` can't take ownership of an existing buffer, so both directions copy the bytes.
+
+use core::str::Utf8Error;
+
+use bytes::Bytes;
+
+use crate::InlineStr;
+
+impl TryFrom<Bytes> for InlineStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: Bytes) -> Result<Self, Self::Error> {
+        crate::from_utf8(&value).map(InlineStr::from)
+    }
+}
+
+impl From<InlineStr> for Bytes {
+    fn from(value: InlineStr) -> Self {
+        Bytes::copy_from_slice(value.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        for text in [
+            "",
+            "short",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = InlineStr::from(text);
+
+            let bytes = Bytes::from(value.clone());
+            assert_eq!(bytes, text.as_bytes());
+            assert_eq!(InlineStr::try_from(bytes).unwrap(), value);
+
+            let from_static = InlineStr::try_from(Bytes::from(text)).unwrap();
+            assert_eq!(Bytes::from(from_static), text.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_bytes_invalid_utf8() {
+        let err = InlineStr::try_from(Bytes::from_static(b"ok\xff")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+    }
+}
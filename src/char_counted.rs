@@ -0,0 +1,196 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::InlineStr;
+
+/// An [`InlineStr`] bundled with its length in `char`s, counted once at construction.
+///
+/// [`CharCounted::char_len`] is O(1), where `chars().count()` walks the whole string. Comparisons
+/// and hashing only look at the string, so a `CharCounted` can be looked up by `&str` in maps.
+///
+/// With the `serde` feature, values are serialized like the wrapped [`InlineStr`].
+#[derive(Clone)]
+pub struct CharCounted {
+    chars: usize,
+    value: InlineStr,
+}
+
+impl CharCounted {
+    /// Wraps `value`, counting its `char`s.
+    pub fn new(value: InlineStr) -> Self {
+        Self {
+            chars: value.chars().count(),
+            value,
+        }
+    }
+
+    /// Returns the cached number of `char`s in the string.
+    pub fn char_len(&self) -> usize {
+        self.chars
+    }
+
+    /// Returns the wrapped string.
+    pub fn into_inner(self) -> InlineStr {
+        self.value
+    }
+}
+
+impl From<InlineStr> for CharCounted {
+    fn from(value: InlineStr) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for CharCounted {
+    fn from(value: &str) -> Self {
+        Self::new(InlineStr::from(value))
+    }
+}
+
+impl From<CharCounted> for InlineStr {
+    fn from(value: CharCounted) -> Self {
+        value.value
+    }
+}
+
+impl Deref for CharCounted {
+    type Target = InlineStr;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl Borrow<str> for CharCounted {
+    fn borrow(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Hash for CharCounted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl PartialEq for CharCounted {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for CharCounted {}
+
+impl PartialOrd for CharCounted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CharCounted {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl PartialEq<InlineStr> for CharCounted {
+    fn eq(&self, other: &InlineStr) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<CharCounted> for InlineStr {
+    fn eq(&self, other: &CharCounted) -> bool {
+        *self == other.value
+    }
+}
+
+impl PartialEq<str> for CharCounted {
+    fn eq(&self, other: &str) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<&str> for CharCounted {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == **other
+    }
+}
+
+impl core::fmt::Debug for CharCounted {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl core::fmt::Display for CharCounted {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.value, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CharCounted {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CharCounted {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        InlineStr::deserialize(deserializer).map(CharCounted::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use super::*;
+    use crate::tests::XorShift;
+
+    #[test]
+    fn test_char_len_matches_chars_count() {
+        let mut rng = XorShift::new(0xc4a5);
+        for _ in 0..200 {
+            let text = rng.string(40);
+            let counted = CharCounted::from(text.as_str());
+            assert_eq!(counted.char_len(), text.chars().count());
+            assert_eq!(counted.char_len(), counted.chars().count());
+            assert_eq!(counted, text.as_str());
+        }
+
+        assert_eq!(CharCounted::from("").char_len(), 0);
+        assert_eq!(CharCounted::from("日本語😀").char_len(), 4);
+    }
+
+    #[test]
+    fn test_lookup_by_str() {
+        let set: BTreeSet<_> = ["b", "a", "héllo"]
+            .into_iter()
+            .map(CharCounted::from)
+            .collect();
+        assert!(set.contains("héllo"));
+        assert!(!set.contains("missing"));
+        assert_eq!(set.first().unwrap(), "a");
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`IntoInlineStr`], a discoverable `collect_inline` shorthand for collecting iterators of
+//! `char`s or strings into an [`InlineStr`].
+
+use crate::InlineStr;
+
+/// Adds [`collect_inline`](IntoInlineStr::collect_inline) to iterators of anything `InlineStr`
+/// can be collected from, such as `char`s and string slices.
+///
+/// ```
+/// use inline_str::IntoInlineStr;
+///
+/// let reversed = "abc".chars().rev().collect_inline();
+/// assert_eq!(reversed, "cba");
+/// ```
+pub trait IntoInlineStr: Iterator {
+    /// Collects the iterator into an [`InlineStr`], same as `collect::<InlineStr>()`.
+    fn collect_inline(self) -> InlineStr;
+}
+
+impl<I> IntoInlineStr for I
+where
+    I: Iterator,
+    InlineStr: FromIterator<I::Item>,
+{
+    fn collect_inline(self) -> InlineStr {
+        self.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::Cow, string::String, vec};
+
+    use super::*;
+    use crate::tests::count_allocations;
+
+    #[test]
+    fn test_collect_inline() {
+        assert_eq!("abc".chars().collect_inline(), "abc");
+        assert_eq!(['a', 'b', 'c'].iter().collect_inline(), "abc");
+        assert_eq!(["ab", "c"].into_iter().collect_inline(), "abc");
+        assert_eq!(
+            vec![String::from("ab"), String::from("c")]
+                .into_iter()
+                .collect_inline(),
+            "abc"
+        );
+        assert_eq!(
+            [Cow::Borrowed("héllo "), Cow::Owned(String::from("wörld"))]
+                .into_iter()
+                .collect_inline(),
+            "héllo wörld"
+        );
+        assert_eq!(
+            [InlineStr::from("ab"), InlineStr::from("c")]
+                .into_iter()
+                .collect_inline(),
+            "abc"
+        );
+        assert_eq!(core::iter::empty::<char>().collect_inline(), "");
+    }
+
+    #[test]
+    fn test_collect_inline_allocations() {
+        assert_eq!(
+            count_allocations(|| {
+                "abc".chars().collect_inline();
+            }),
+            0
+        );
+
+        let long = "a much longer string".chars().collect_inline();
+        assert!(long.is_heap_allocated());
+        assert_eq!(long, "a much longer string");
+    }
+}
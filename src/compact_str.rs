@@ -0,0 +1,57 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use compact_str::CompactString;
+
+use crate::InlineStr;
+
+impl From<CompactString> for InlineStr {
+    fn from(value: CompactString) -> Self {
+        InlineStr::from(value.as_str())
+    }
+}
+
+impl From<&CompactString> for InlineStr {
+    fn from(value: &CompactString) -> Self {
+        InlineStr::from(value.as_str())
+    }
+}
+
+impl From<InlineStr> for CompactString {
+    fn from(value: InlineStr) -> Self {
+        CompactString::from(value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_str_roundtrip() {
+        for text in [
+            "",
+            "short",
+            "日本語",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = InlineStr::from(text);
+
+            let converted = CompactString::from(value.clone());
+            assert_eq!(converted, text);
+            assert_eq!(InlineStr::from(&converted), value);
+            assert_eq!(InlineStr::from(converted), value);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Heap usage is reported by [`InlineStr::heap_size`]. `inline-array` doesn't expose the
+//! identity of shared buffers to a [`Context`], so every clone counts its buffer in full.
+
+use deepsize::{Context, DeepSizeOf};
+
+use crate::InlineStr;
+
+impl DeepSizeOf for InlineStr {
+    fn deep_size_of_children(&self, _context: &mut Context) -> usize {
+        self.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_size_of() {
+        let short = InlineStr::from("short");
+        assert_eq!(short.deep_size_of(), size_of::<InlineStr>());
+
+        let long = InlineStr::from("a much longer string that is stored on the heap");
+        assert!(long.deep_size_of() >= size_of::<InlineStr>() + long.len());
+        assert_eq!(long.deep_size_of(), long.total_size());
+    }
+
+    #[test]
+    fn test_deep_size_of_vec() {
+        let long = InlineStr::from("a much longer string that is stored on the heap");
+        let mut values = Vec::with_capacity(4);
+        values.extend([InlineStr::from("a"), long.clone(), long.clone()]);
+
+        let expected =
+            size_of::<Vec<InlineStr>>() + 4 * size_of::<InlineStr>() + 2 * long.heap_size();
+        assert_eq!(values.deep_size_of(), expected);
+    }
+}
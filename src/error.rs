@@ -0,0 +1,76 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors returned by constructors that check their input, like
+//! [`InlineStr::try_inline`](crate::InlineStr::try_inline) and
+//! [`InlineStr::from_ascii`](crate::InlineStr::from_ascii).
+
+use core::fmt;
+
+/// The error returned by [`InlineStr::try_inline`](crate::InlineStr::try_inline) when the string
+/// doesn't fit inline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    len: usize,
+}
+
+impl CapacityError {
+    pub(crate) fn new(len: usize) -> Self {
+        Self { len }
+    }
+
+    /// The length, in bytes, of the string that didn't fit.
+    pub fn attempted_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string of {} bytes exceeds the inline capacity of {} bytes",
+            self.len,
+            crate::InlineStr::INLINE_CAPACITY
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// The error returned by [`InlineStr::from_ascii`](crate::InlineStr::from_ascii) when the input
+/// contains a non-ASCII byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsciiError {
+    position: usize,
+}
+
+impl AsciiError {
+    pub(crate) fn new(position: usize) -> Self {
+        Self { position }
+    }
+
+    /// The index of the first non-ASCII byte.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "non-ASCII byte at position {}", self.position)
+    }
+}
+
+impl core::error::Error for AsciiError {}
@@ -0,0 +1,148 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::{self, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::InlineStr;
+
+/// Upper bound on the bytes reserved from a length prefix before the payload is actually read.
+const MAX_PREALLOC: usize = 4096;
+
+/// Serializes the wrapped [`InlineStr`] as a tuple of its `u32` length followed by its UTF-8
+/// bytes, for formats without native string framing.
+///
+/// In formats that don't frame tuples, like `bincode`, this is exactly a `u32` followed by the raw
+/// bytes. Serializing a string longer than `u32::MAX` bytes fails.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedLayout(pub InlineStr);
+
+impl Serialize for FixedLayout {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.0.as_bytes();
+        let len = u32::try_from(bytes.len())
+            .map_err(|_| ser::Error::custom("string is longer than u32::MAX bytes"))?;
+
+        let mut tuple = serializer.serialize_tuple(bytes.len() + 1)?;
+        tuple.serialize_element(&len)?;
+        for byte in bytes {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for FixedLayout {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The length isn't known upfront, the visitor stops reading after the last byte
+        deserializer.deserialize_tuple(usize::MAX, FixedLayoutVisitor)
+    }
+}
+
+struct FixedLayoutVisitor;
+
+impl<'de> Visitor<'de> for FixedLayoutVisitor {
+    type Value = FixedLayout;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a u32 length followed by that many UTF-8 bytes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let len: u32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let len = len as usize;
+
+        // Short strings are read straight into a stack buffer, skipping the intermediate `Vec`
+        let mut inline = [0; InlineStr::INLINE_CAPACITY];
+        let mut heap = Vec::new();
+        let buf = if len <= InlineStr::INLINE_CAPACITY {
+            for (idx, byte) in inline[..len].iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(idx + 1, &self))?;
+            }
+            &inline[..len]
+        } else {
+            // The length prefix is untrusted, so only reserve a bounded amount up front and let the
+            // buffer grow as bytes actually arrive.
+            heap.reserve(len.min(MAX_PREALLOC));
+            for idx in 0..len {
+                let byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(idx + 1, &self))?;
+                heap.push(byte);
+            }
+            &heap[..]
+        };
+
+        crate::from_utf8(buf)
+            .map(|s| FixedLayout(InlineStr::from(s)))
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+
+    use super::*;
+
+    #[test]
+    fn test_fixed_layout_roundtrip() {
+        let config = config::standard().with_fixed_int_encoding();
+        for text in [
+            "",
+            "short",
+            "日本語",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = FixedLayout(InlineStr::from(text));
+
+            let bytes = bincode::serde::encode_to_vec(&value, config).unwrap();
+            let mut expected = (text.len() as u32).to_le_bytes().to_vec();
+            expected.extend_from_slice(text.as_bytes());
+            assert_eq!(bytes, expected);
+
+            let (decoded, read): (FixedLayout, _) =
+                bincode::serde::decode_from_slice(&bytes, config).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(read, bytes.len());
+        }
+    }
+
+    #[test]
+    fn test_fixed_layout_invalid() {
+        let config = config::standard().with_fixed_int_encoding();
+
+        let invalid_utf8 = [2, 0, 0, 0, b'o', 0xff];
+        assert!(
+            bincode::serde::decode_from_slice::<FixedLayout, _>(&invalid_utf8, config).is_err()
+        );
+
+        let truncated = [4, 0, 0, 0, b'a', b'b'];
+        assert!(bincode::serde::decode_from_slice::<FixedLayout, _>(&truncated, config).is_err());
+
+        // A huge length prefix followed by a short payload fails without reserving the claimed size
+        let huge_len = [0xff, 0xff, 0xff, 0xff, b'a'];
+        assert!(bincode::serde::decode_from_slice::<FixedLayout, _>(&huge_len, config).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Heap usage is reported by [`InlineStr::heap_size`], so every clone of a shared string counts
+//! its buffer in full.
+
+use get_size::GetSize;
+
+use crate::InlineStr;
+
+impl GetSize for InlineStr {
+    fn get_heap_size(&self) -> usize {
+        self.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_size() {
+        let short = InlineStr::from("short");
+        assert_eq!(short.get_heap_size(), 0);
+        assert_eq!(short.get_size(), size_of::<InlineStr>());
+
+        let long = InlineStr::from("a much longer string that is stored on the heap");
+        assert_eq!(long.get_heap_size(), long.heap_size());
+        assert!(long.get_heap_size() >= long.len());
+        assert_eq!(long.get_size(), long.total_size());
+    }
+
+    #[test]
+    fn test_get_size_vec() {
+        let long = InlineStr::from("a much longer string that is stored on the heap");
+        let mut values = Vec::with_capacity(4);
+        values.extend([InlineStr::from("a"), long.clone(), long.clone()]);
+
+        let expected = 4 * size_of::<InlineStr>() + 2 * long.heap_size();
+        assert_eq!(values.get_heap_size(), expected);
+    }
+}
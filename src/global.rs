@@ -0,0 +1,147 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A process-wide interner, sharded to keep lock contention low.
+
+use std::{
+    collections::HashSet,
+    sync::{LazyLock, RwLock},
+};
+
+use crate::{fnv1a, InlineStr};
+
+const SHARDS: usize = 16;
+
+static INTERNER: LazyLock<[RwLock<HashSet<InlineStr>>; SHARDS]> =
+    LazyLock::new(|| std::array::from_fn(|_| RwLock::default()));
+
+fn shard(s: &str) -> &'static RwLock<HashSet<InlineStr>> {
+    &INTERNER[fnv1a(s.as_bytes(), 0) as usize % SHARDS]
+}
+
+/// Returns the canonical copy of `s` from the global interner, interning it if it isn't already.
+///
+/// Like [`InlineStrInterner`](crate::InlineStrInterner), strings that are stored inline are
+/// returned as is, without being interned. Calls with equal strings usually return values sharing
+/// the same buffer, subject to `inline-array`'s refcount limit: once a buffer's refcount
+/// saturates, further clones get a fresh copy of the bytes instead.
+pub fn intern(s: &str) -> InlineStr {
+    if InlineStr::will_inline(s.len()) {
+        return InlineStr::from(s);
+    }
+
+    let shard = shard(s);
+    if let Some(interned) = shard.read().unwrap().get(s) {
+        return interned.clone();
+    }
+
+    // Allocate before taking the write lock, another thread might still win the race
+    let value = InlineStr::from(s);
+    let mut strings = shard.write().unwrap();
+    if let Some(interned) = strings.get(s) {
+        return interned.clone();
+    }
+    strings.insert(value.clone());
+    value
+}
+
+/// Returns the number of strings in the global interner.
+pub fn interned_count() -> usize {
+    INTERNER
+        .iter()
+        .map(|shard| shard.read().unwrap().len())
+        .sum()
+}
+
+/// Removes all strings from the global interner, releasing their memory once no other copies
+/// remain. Previously returned values stay valid, but are no longer shared with new ones.
+pub fn clear_interned() {
+    for shard in INTERNER.iter() {
+        let mut strings = shard.write().unwrap();
+        strings.clear();
+        strings.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, thread};
+
+    use super::*;
+
+    // The interner is global, so tests that clear it can't run alongside the others
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_intern_lookup() {
+        let _guard = LOCK.lock().unwrap();
+
+        let key = "global interner lookup key, long enough for the heap";
+        let a = intern(key);
+        let b = intern(&String::from(key));
+        assert_eq!(a, key);
+        assert!(a.is_heap_allocated());
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&InlineStr::from(key)));
+
+        assert_eq!(intern("short"), "short");
+        assert!(interned_count() >= 1);
+    }
+
+    #[test]
+    fn test_intern_concurrent() {
+        let _guard = LOCK.lock().unwrap();
+
+        let keys: Vec<String> = (0..64)
+            .map(|i| format!("concurrently interned key number {i}"))
+            .collect();
+
+        let results: Vec<Vec<InlineStr>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|offset| {
+                    let keys = &keys;
+                    scope.spawn(move || {
+                        (0..keys.len())
+                            .map(|i| intern(&keys[(i + offset) % keys.len()]))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for (offset, values) in results.iter().enumerate() {
+            for (i, value) in values.iter().enumerate() {
+                let key = &keys[(i + offset) % keys.len()];
+                assert_eq!(value, key);
+                assert!(value.ptr_eq(&intern(key)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_clear_interned() {
+        let _guard = LOCK.lock().unwrap();
+
+        let key = "a key that is about to be cleared from the interner";
+        let before = intern(key);
+        clear_interned();
+        assert_eq!(interned_count(), 0);
+
+        let after = intern(key);
+        assert_eq!(before, after);
+        assert!(!before.ptr_eq(&after));
+        assert_eq!(interned_count(), 1);
+    }
+}
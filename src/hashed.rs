@@ -0,0 +1,223 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::{
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasherDefault, DefaultHasher};
+
+use crate::InlineStr;
+
+/// An [`InlineStr`] bundled with its hash, computed once at construction.
+///
+/// Hashing a `PreHashed` only writes the cached `u64`, which makes repeated lookups of the
+/// same keys cheap. Values are only comparable if they were hashed with the same [`BuildHasher`].
+///
+/// `PreHashed` doesn't implement `Borrow<str>`, as its hash doesn't match the hash of the
+/// string itself. Look keys up with another `PreHashed` instead.
+///
+/// With the `serde` feature, values are serialized like the wrapped [`InlineStr`], and
+/// deserialized values are hashed like [`PreHashed::from`].
+#[derive(Clone)]
+pub struct PreHashed {
+    hash: u64,
+    value: InlineStr,
+}
+
+impl PreHashed {
+    /// Wraps `value`, hashing it with `build_hasher`.
+    pub fn new<S: BuildHasher>(value: InlineStr, build_hasher: &S) -> Self {
+        Self {
+            hash: build_hasher.hash_one(&value),
+            value,
+        }
+    }
+
+    /// Returns the cached hash.
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the wrapped string.
+    pub fn into_inner(self) -> InlineStr {
+        self.value
+    }
+}
+
+#[cfg(feature = "std")]
+/// Wraps the string, hashing it with a fixed [`DefaultHasher`] rather than a map's own
+/// [`BuildHasher`]. The hash is stable within a build of the program, but not across Rust versions.
+impl From<InlineStr> for PreHashed {
+    fn from(value: InlineStr) -> Self {
+        Self::new(value, &BuildHasherDefault::<DefaultHasher>::default())
+    }
+}
+
+impl Hash for PreHashed {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl PartialEq for PreHashed {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.value == other.value
+    }
+}
+
+impl Eq for PreHashed {}
+
+impl PartialEq<InlineStr> for PreHashed {
+    fn eq(&self, other: &InlineStr) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<PreHashed> for InlineStr {
+    fn eq(&self, other: &PreHashed) -> bool {
+        *self == other.value
+    }
+}
+
+impl PartialEq<str> for PreHashed {
+    fn eq(&self, other: &str) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<&str> for PreHashed {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == **other
+    }
+}
+
+impl Deref for PreHashed {
+    type Target = InlineStr;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl core::fmt::Debug for PreHashed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl core::fmt::Display for PreHashed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.value, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PreHashed {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<'de> serde::Deserialize<'de> for PreHashed {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        InlineStr::deserialize(deserializer).map(PreHashed::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        hash::{BuildHasher, RandomState},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_equal_strings_equal_hashes() {
+        let hasher = RandomState::new();
+
+        let a = PreHashed::new(InlineStr::from("the quick brown fox"), &hasher);
+        let b = PreHashed::new(InlineStr::from("the quick brown fox"), &hasher);
+        let c = PreHashed::new(InlineStr::from("jumps over"), &hasher);
+
+        assert_eq!(a.hash_value(), b.hash_value());
+        assert_eq!(a.hash_value(), hasher.hash_one("the quick brown fox"));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+    }
+
+    #[test]
+    fn test_map_lookup() {
+        let hasher = RandomState::new();
+        let key = |s: &str| PreHashed::new(InlineStr::from(s), &hasher);
+
+        let mut map = HashMap::new();
+        map.insert(key("a"), 1);
+        map.insert(key("a much longer key that lives on the heap"), 2);
+
+        assert_eq!(map.get(&key("a")), Some(&1));
+        assert_eq!(
+            map.get(&key("a much longer key that lives on the heap")),
+            Some(&2)
+        );
+        assert_eq!(map.get(&key("missing")), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fixed_hasher() {
+        let a = PreHashed::from(InlineStr::from("the quick brown fox"));
+        let b = PreHashed::from(InlineStr::from("the quick brown fox"));
+        assert_eq!(a.hash_value(), b.hash_value());
+        assert_eq!(a, b);
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        assert_eq!(map.get(&b), Some(&1));
+        assert_eq!(map.get(&PreHashed::from(InlineStr::from("fox"))), None);
+    }
+
+    #[test]
+    fn test_eq_plain_strings() {
+        let value = InlineStr::from("a much longer key that lives on the heap");
+        let hashed = PreHashed::new(value.clone(), &RandomState::new());
+
+        assert_eq!(hashed, value);
+        assert_eq!(value, hashed);
+        assert_eq!(hashed, *"a much longer key that lives on the heap");
+        assert_eq!(hashed, "a much longer key that lives on the heap");
+        assert_ne!(hashed, "a");
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn test_serde_transparent() {
+        for text in ["a", "a much longer key that lives on the heap"] {
+            let value = InlineStr::from(text);
+            let hashed = PreHashed::from(value.clone());
+
+            let json = serde_json::to_string(&hashed).unwrap();
+            assert_eq!(json, serde_json::to_string(&value).unwrap());
+
+            let back = serde_json::from_str::<PreHashed>(&json).unwrap();
+            assert_eq!(back, hashed);
+            assert_eq!(back.hash_value(), hashed.hash_value());
+        }
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::InlineStr;
+
+/// A string like [`InlineStr`] that stores strings of up to `CAP` bytes inline, instead of
+/// [`InlineStr::INLINE_CAPACITY`].
+///
+/// Longer strings are stored in an [`InlineStr`], and shared between clones. Values compare and
+/// hash like their string contents, so strings of different capacities, [`InlineStr`]s and `str`
+/// can be used interchangeably as map keys.
+///
+/// `CAP` must be at most 255, larger capacities fail to compile:
+///
+/// ```compile_fail
+/// let _ = inline_str::InlineStrN::<256>::from("too large");
+/// ```
+#[derive(Clone)]
+pub struct InlineStrN<const CAP: usize>(Repr<CAP>);
+
+#[derive(Clone)]
+enum Repr<const CAP: usize> {
+    Inline { len: u8, buf: [u8; CAP] },
+    Heap(InlineStr),
+}
+
+impl<const CAP: usize> InlineStrN<CAP> {
+    /// The longest string, in bytes, that is stored inline.
+    pub const INLINE_CAPACITY: usize = {
+        assert!(
+            CAP <= u8::MAX as usize,
+            "inline capacity must be at most 255"
+        );
+        CAP
+    };
+
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Returns `true` if the string is stored inline, without a heap allocation.
+    pub fn is_inline(&self) -> bool {
+        match &self.0 {
+            Repr::Inline { .. } => true,
+            Repr::Heap(value) => value.is_inline(),
+        }
+    }
+
+    /// Converts the string to another inline capacity, sharing its heap buffer if it has one.
+    pub fn to_capacity<const M: usize>(&self) -> InlineStrN<M> {
+        match &self.0 {
+            Repr::Heap(value) => InlineStrN::from(value.clone()),
+            Repr::Inline { .. } => InlineStrN::from(self.as_str()),
+        }
+    }
+}
+
+impl<const CAP: usize> Deref for InlineStrN<CAP> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.0 {
+            // Safety: `buf` is only ever filled from a `str`
+            Repr::Inline { len, buf } => unsafe {
+                core::str::from_utf8_unchecked(&buf[..*len as usize])
+            },
+            Repr::Heap(value) => value,
+        }
+    }
+}
+
+impl<const CAP: usize> From<&str> for InlineStrN<CAP> {
+    fn from(value: &str) -> Self {
+        if value.len() > Self::INLINE_CAPACITY {
+            return Self(Repr::Heap(InlineStr::from(value)));
+        }
+
+        let mut buf = [0; CAP];
+        buf[..value.len()].copy_from_slice(value.as_bytes());
+        Self(Repr::Inline {
+            len: value.len() as u8,
+            buf,
+        })
+    }
+}
+
+impl<const CAP: usize> From<String> for InlineStrN<CAP> {
+    fn from(value: String) -> Self {
+        if value.len() > Self::INLINE_CAPACITY {
+            Self(Repr::Heap(InlineStr::from(value)))
+        } else {
+            Self::from(value.as_str())
+        }
+    }
+}
+
+impl<const CAP: usize> From<InlineStr> for InlineStrN<CAP> {
+    fn from(value: InlineStr) -> Self {
+        if value.len() > Self::INLINE_CAPACITY {
+            Self(Repr::Heap(value))
+        } else {
+            Self::from(value.as_str())
+        }
+    }
+}
+
+impl<const CAP: usize> From<InlineStrN<CAP>> for InlineStr {
+    fn from(value: InlineStrN<CAP>) -> Self {
+        match value.0 {
+            Repr::Heap(value) => value,
+            Repr::Inline { .. } => InlineStr::from(value.as_str()),
+        }
+    }
+}
+
+impl<const CAP: usize> Default for InlineStrN<CAP> {
+    fn default() -> Self {
+        Self::from("")
+    }
+}
+
+impl<const CAP: usize> Borrow<str> for InlineStrN<CAP> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<const CAP: usize> AsRef<str> for InlineStrN<CAP> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<const CAP: usize> Hash for InlineStrN<CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<const CAP: usize, const M: usize> PartialEq<InlineStrN<M>> for InlineStrN<CAP> {
+    fn eq(&self, other: &InlineStrN<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const CAP: usize> Eq for InlineStrN<CAP> {}
+
+impl<const CAP: usize, const M: usize> PartialOrd<InlineStrN<M>> for InlineStrN<CAP> {
+    fn partial_cmp(&self, other: &InlineStrN<M>) -> Option<Ordering> {
+        Some(self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl<const CAP: usize> Ord for InlineStrN<CAP> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+macro_rules! impl_eq_str {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<const CAP: usize> PartialEq<$ty> for InlineStrN<CAP> {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.as_str() == Borrow::<str>::borrow(other)
+                }
+            }
+
+            impl<const CAP: usize> PartialEq<InlineStrN<CAP>> for $ty {
+                fn eq(&self, other: &InlineStrN<CAP>) -> bool {
+                    Borrow::<str>::borrow(self) == other.as_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_eq_str!(str, &str, String, InlineStr);
+
+impl<const CAP: usize> fmt::Debug for InlineStrN<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const CAP: usize> fmt::Display for InlineStrN<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const CAP: usize> serde::Serialize for InlineStrN<CAP> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const CAP: usize> serde::Deserialize<'de> for InlineStrN<CAP> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrVisitor<const CAP: usize>;
+
+        impl<const CAP: usize> serde::de::Visitor<'_> for StrVisitor<CAP> {
+            type Value = InlineStrN<CAP>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(InlineStrN::from(value))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(InlineStrN::from(value))
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        hash::{BuildHasher, RandomState},
+    };
+
+    use super::*;
+
+    const KEY_40: &str = "a forty byte key, just over the inline..";
+
+    #[test]
+    fn test_larger_capacity_stays_inline() {
+        assert_eq!(KEY_40.len(), 40);
+
+        let key = InlineStrN::<48>::from(KEY_40);
+        assert!(key.is_inline());
+        assert_eq!(key, KEY_40);
+        assert!(!InlineStr::from(KEY_40).is_inline());
+
+        let long = InlineStrN::<16>::from(KEY_40);
+        assert!(!long.is_inline());
+        assert!(InlineStr::from(long.clone()).ptr_eq(&InlineStr::from(long)));
+
+        assert_eq!(InlineStrN::<48>::INLINE_CAPACITY, 48);
+        assert!(InlineStrN::<48>::default().is_empty());
+    }
+
+    fn check_boundary<const N: usize>() {
+        let source = "x".repeat(N + 1);
+        let fits = InlineStrN::<N>::from(&source[..N]);
+        assert!(fits.is_inline());
+        assert_eq!(fits, &source[..N]);
+
+        let spills = InlineStrN::<N>::from(source.as_str());
+        assert_eq!(spills.is_inline(), InlineStr::will_inline(N + 1));
+        assert_eq!(spills, source.as_str());
+
+        // A multibyte char that would straddle the boundary spills the whole string
+        let straddling = format!("{}é", &source[..N - 1]);
+        let straddling = InlineStrN::<N>::from(straddling.as_str());
+        assert!(matches!(straddling.0, Repr::Heap(_)));
+    }
+
+    #[test]
+    fn test_capacity_boundary() {
+        check_boundary::<1>();
+        check_boundary::<7>();
+        check_boundary::<8>();
+        check_boundary::<16>();
+        check_boundary::<255>();
+        assert!(InlineStrN::<0>::from("").is_inline());
+    }
+
+    #[test]
+    fn test_cross_capacity() {
+        let hasher = RandomState::new();
+        let small = InlineStrN::<16>::from(KEY_40);
+        let large: InlineStrN<48> = small.to_capacity();
+        let inline_str = InlineStr::from(KEY_40);
+
+        assert_eq!(small, large);
+        assert_eq!(large, inline_str);
+        assert_eq!(inline_str, small);
+        assert_eq!(hasher.hash_one(&small), hasher.hash_one(&large));
+        assert_eq!(hasher.hash_one(&large), hasher.hash_one(&inline_str));
+        assert_eq!(hasher.hash_one(&small), hasher.hash_one(KEY_40));
+
+        let (a, b) = (InlineStrN::<16>::from("a"), InlineStrN::<48>::from("b"));
+        assert!(a < b);
+        assert!(large.to_capacity::<16>() == small);
+
+        let map: HashMap<InlineStrN<48>, u32> = HashMap::from([(large, 1)]);
+        assert_eq!(map.get(KEY_40), Some(&1));
+    }
+
+    #[test]
+    fn test_conversions() {
+        for text in [
+            "",
+            "short",
+            KEY_40,
+            "a string that doesn't even fit in the larger capacity",
+        ] {
+            let value = InlineStrN::<48>::from(String::from(text));
+            assert_eq!(value, text);
+            assert_eq!(InlineStr::from(value.clone()), text);
+            assert_eq!(InlineStrN::<48>::from(InlineStr::from(text)), value);
+            assert_eq!(format!("{value}|{value:?}"), format!("{text}|{text:?}"));
+        }
+    }
+
+    #[test]
+    fn test_default_alias_unchanged() {
+        assert_eq!(size_of::<InlineStr>(), 8);
+        assert_eq!(InlineStr::INLINE_CAPACITY, 7);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let value = InlineStrN::<48>::from(KEY_40);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, format!("\"{KEY_40}\""));
+        assert_eq!(
+            serde_json::from_str::<InlineStrN<48>>(&json).unwrap(),
+            value
+        );
+        assert_eq!(
+            serde_json::from_reader::<_, InlineStrN<16>>(json.as_bytes()).unwrap(),
+            value
+        );
+    }
+}
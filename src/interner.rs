@@ -0,0 +1,133 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::InlineStr;
+
+/// Deduplicates strings, so that all interned copies of a heap-allocated string share one buffer.
+///
+/// Strings short enough to be stored inline gain nothing from sharing, so they aren't stored
+/// unless [`InlineStrInterner::intern_inline`] is enabled.
+#[doc(alias = "Interner")]
+#[derive(Clone, Debug, Default)]
+pub struct InlineStrInterner {
+    strings: HashSet<InlineStr>,
+    intern_inline: bool,
+}
+
+impl InlineStrInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether strings that are stored inline are interned as well.
+    pub fn intern_inline(mut self, intern_inline: bool) -> Self {
+        self.intern_inline = intern_inline;
+        self
+    }
+
+    /// Returns the canonical copy of `s`, interning it if it isn't already.
+    pub fn intern(&mut self, s: &str) -> InlineStr {
+        if !self.intern_inline && InlineStr::will_inline(s.len()) {
+            return InlineStr::from(s);
+        }
+
+        if let Some(interned) = self.strings.get(s) {
+            return interned.clone();
+        }
+
+        let interned = InlineStr::from(s);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// Returns the canonical copy of `s` if it was interned.
+    pub fn get(&self, s: &str) -> Option<InlineStr> {
+        self.strings.get(s).cloned()
+    }
+
+    /// Returns the number of interned strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no strings are interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Removes all interned strings. Previously returned values stay valid.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG: &str = "a much longer string that is stored on the heap";
+
+    #[test]
+    fn test_intern_shares_buffers() {
+        let mut interner = InlineStrInterner::new();
+
+        let a = interner.intern(LONG);
+        let b = interner.intern(&String::from(LONG));
+        assert!(a.is_heap_allocated());
+        assert!(a.ptr_eq(&b));
+        assert!(interner.get(LONG).unwrap().ptr_eq(&a));
+        assert_eq!(interner.get("missing from the interner entirely"), None);
+    }
+
+    #[test]
+    fn test_intern_repeated() {
+        let mut interner = InlineStrInterner::new();
+        let first = interner.intern(LONG);
+        let capacity = interner.strings.capacity();
+
+        for _ in 0..1000 {
+            assert!(interner.intern(LONG).ptr_eq(&first));
+        }
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.strings.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_intern_inline_policy() {
+        let mut interner = InlineStrInterner::new();
+        assert_eq!(interner.intern("short"), "short");
+        assert_eq!(interner.get("short"), None);
+        assert!(interner.is_empty());
+
+        let mut interner = InlineStrInterner::new().intern_inline(true);
+        assert_eq!(interner.intern("short"), "short");
+        assert_eq!(interner.get("short").unwrap(), "short");
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut interner = InlineStrInterner::new();
+        let a = interner.intern(LONG);
+        interner.clear();
+
+        assert!(interner.is_empty());
+        assert_eq!(interner.get(LONG), None);
+        assert_eq!(a, LONG);
+        assert!(!interner.intern(LONG).ptr_eq(&a));
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between [`InlineStr`] and `serde_json::Value`.
+//!
+//! Strings convert into `Value::String`, and `TryFrom<Value>` returns the original `Value` when
+//! it isn't a string. [`InlineStr::from_json_scalar`] also stringifies numbers and bools, and
+//! `PartialEq` compares against string values only.
+
+use core::fmt::Write;
+
+use serde_json::Value;
+
+use crate::{builder::Builder, InlineStr};
+
+impl InlineStr {
+    /// Converts a JSON string, number or bool into a string, formatting non-strings like
+    /// `serde_json` does. Returns `None` for null, arrays and objects.
+    ///
+    /// ```
+    /// # use inline_str::InlineStr;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(InlineStr::from_json_scalar(&json!("id")).unwrap(), "id");
+    /// assert_eq!(InlineStr::from_json_scalar(&json!(1.5)).unwrap(), "1.5");
+    /// assert_eq!(InlineStr::from_json_scalar(&json!(null)), None);
+    /// ```
+    pub fn from_json_scalar(value: &Value) -> Option<InlineStr> {
+        let mut builder = Builder::new();
+        match value {
+            Value::String(s) => return Some(InlineStr::from(s)),
+            Value::Number(n) => write!(builder, "{n}").ok()?,
+            Value::Bool(b) => write!(builder, "{b}").ok()?,
+            Value::Null | Value::Array(_) | Value::Object(_) => return None,
+        }
+        Some(builder.finish())
+    }
+}
+
+impl From<InlineStr> for Value {
+    fn from(value: InlineStr) -> Self {
+        Value::String(value.as_str().into())
+    }
+}
+
+impl From<&InlineStr> for Value {
+    fn from(value: &InlineStr) -> Self {
+        Value::String(value.as_str().into())
+    }
+}
+
+/// Fails with the original value if it isn't a string, see [`InlineStr::from_json_scalar`] to
+/// also accept numbers and bools.
+impl TryFrom<Value> for InlineStr {
+    type Error = Value;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(InlineStr::from(s)),
+            other => Err(other),
+        }
+    }
+}
+
+/// Only equal to string values.
+impl PartialEq<Value> for InlineStr {
+    fn eq(&self, other: &Value) -> bool {
+        other.as_str() == Some(self.as_str())
+    }
+}
+
+impl PartialEq<InlineStr> for Value {
+    fn eq(&self, other: &InlineStr) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_value_conversions() {
+        for text in ["", "id", "a much longer string that is stored on the heap"] {
+            let value = InlineStr::from(text);
+            assert_eq!(Value::from(value.clone()), json!(text));
+            assert_eq!(Value::from(&value), json!(text));
+            assert_eq!(InlineStr::try_from(json!(text)).unwrap(), value);
+        }
+
+        assert_eq!(InlineStr::try_from(json!(1)), Err(json!(1)));
+        assert_eq!(InlineStr::try_from(json!(null)), Err(Value::Null));
+        assert_eq!(InlineStr::try_from(json!(["x"])), Err(json!(["x"])));
+    }
+
+    #[test]
+    fn test_from_json_scalar() {
+        let scalar = |value: Value| InlineStr::from_json_scalar(&value);
+
+        assert_eq!(scalar(json!("héllo")).unwrap(), "héllo");
+        assert_eq!(scalar(json!(42)).unwrap(), "42");
+        assert_eq!(scalar(json!(-7)).unwrap(), "-7");
+        assert_eq!(scalar(json!(u64::MAX)).unwrap(), u64::MAX.to_string());
+        assert_eq!(scalar(json!(0.25)).unwrap(), "0.25");
+        assert_eq!(scalar(json!(false)).unwrap(), "false");
+        assert_eq!(scalar(json!(null)), None);
+        assert_eq!(scalar(json!([1])), None);
+        assert_eq!(scalar(json!({"a": 1})), None);
+    }
+
+    #[test]
+    fn test_eq_value() {
+        let key = InlineStr::from("x");
+        assert_eq!(key, json!("x"));
+        assert_eq!(json!("x"), key);
+        assert_ne!(key, json!("y"));
+        assert_ne!(InlineStr::from("1"), json!(1));
+        assert_ne!(json!(null), InlineStr::from("null"));
+
+        let doc = json!({"x": "value"});
+        assert_eq!(doc[key.as_str()], InlineStr::from("value"));
+    }
+}
@@ -12,134 +12,2940 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::str;
-use std::{borrow::Cow, ops::Deref};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    ffi::{CString, NulError},
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ffi::CStr,
+    fmt::Write,
+    ops::{
+        Bound, Deref, Index, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+        RangeToInclusive,
+    },
+    str,
+    str::Utf8Error,
+};
+#[cfg(feature = "std")]
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use builder::Builder;
 use inline_array::InlineArray;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod builder;
+#[cfg(feature = "bytes")]
+mod bytes;
+mod char_counted;
+mod collect;
+#[cfg(feature = "compact_str")]
+mod compact_str;
+#[cfg(feature = "deepsize")]
+mod deepsize;
+mod error;
+#[cfg(feature = "serde")]
+mod fixed_layout;
+#[cfg(feature = "get-size")]
+mod get_size;
+#[cfg(feature = "std")]
+mod global;
+mod hashed;
+mod inline_str_n;
+#[cfg(feature = "std")]
+mod interner;
+#[cfg(feature = "json")]
+mod json;
+mod map_ext;
+mod pattern;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(feature = "serde_with")]
+mod serde_with;
+#[cfg(feature = "smol_str")]
+mod smol_str;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "serde")]
+mod with_storage;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+#[cfg(feature = "arrow")]
+pub use arrow::collect_string_array;
+pub use char_counted::CharCounted;
+pub use collect::IntoInlineStr;
+pub use error::{AsciiError, CapacityError};
+#[cfg(feature = "serde")]
+pub use fixed_layout::FixedLayout;
+#[cfg(feature = "std")]
+pub use global::{clear_interned, intern, interned_count};
+pub use hashed::PreHashed;
+pub use inline_str_n::InlineStrN;
+#[cfg(feature = "std")]
+pub use interner::InlineStrInterner;
+pub use map_ext::InlineStrMapExt;
+pub use pattern::Pattern;
+#[cfg(feature = "stats")]
+pub use stats::{reset_stats, stats, Stats};
+#[cfg(feature = "serde")]
+pub use with_storage::WithStorage;
+#[cfg(feature = "zeroize")]
+pub use zeroize::SecretInlineStr;
+
+/// An immutable string that stores short values inline and shares longer ones between clones.
+///
+/// Strings short enough to be stored inline always are, no matter how they were constructed, so
+/// there is never a need to "shrink" a value back into inline storage.
+///
+/// Ordering compares the underlying UTF-8 bytes, which is guaranteed to be the same as comparing
+/// the strings' Unicode scalar values one by one.
+///
+/// An `InlineStr` is always 8 bytes. `InlineArray` has no niche, so `Option<InlineStr>` needs a
+/// separate discriminant, and is 16 bytes on 64-bit targets.
+#[derive(Eq, PartialOrd, Ord, Clone)]
 pub struct InlineStr {
     inner: InlineArray,
 }
 
-impl std::fmt::Display for InlineStr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&**self, f)
+impl InlineStr {
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self
     }
-}
 
-impl std::fmt::Debug for InlineStr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(&**self, f)
+    /// Returns the UTF-8 bytes of the string.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner
     }
-}
 
-impl std::hash::Hash for InlineStr {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let as_str: &str = &*self;
-        as_str.hash(state);
+    /// Creates a string from bytes without checking that they're valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be valid UTF-8, see [`str::from_utf8_unchecked`].
+    pub unsafe fn from_utf8_unchecked(bytes: &[u8]) -> InlineStr {
+        Self {
+            inner: new_inner(bytes),
+        }
     }
-}
 
-impl From<String> for InlineStr {
-    fn from(value: String) -> Self {
-        Self {
-            inner: InlineArray::from(value.as_bytes()),
+    /// Creates a string from everything before the first NUL byte in `bytes`, for NUL-padded
+    /// fields in fixed-size structs.
+    ///
+    /// Unlike [`CStr::from_bytes_until_nul`], a missing NUL isn't an error, a field that uses its
+    /// full width has no terminator, and the whole slice is used instead. Anything after the
+    /// first NUL is ignored.
+    ///
+    /// ```
+    /// # use inline_str::InlineStr;
+    /// let mut name = [0; 16];
+    /// name[..5].copy_from_slice(b"hello");
+    /// assert_eq!(InlineStr::from_bytes_until_nul(&name).unwrap(), "hello");
+    /// ```
+    pub fn from_bytes_until_nul(bytes: &[u8]) -> Result<InlineStr, Utf8Error> {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        from_utf8(&bytes[..end]).map(InlineStr::from)
+    }
+
+    /// Creates a string from ASCII bytes, which is cheaper to check than general UTF-8.
+    ///
+    /// ```
+    /// # use inline_str::InlineStr;
+    /// assert_eq!(InlineStr::from_ascii(b"GET").unwrap(), "GET");
+    /// assert_eq!(InlineStr::from_ascii("GÉT".as_bytes()).unwrap_err().position(), 1);
+    /// ```
+    pub fn from_ascii(bytes: &[u8]) -> Result<InlineStr, AsciiError> {
+        if !bytes.is_ascii() {
+            let position = bytes.iter().position(|b| !b.is_ascii()).unwrap_or_default();
+            return Err(AsciiError::new(position));
         }
+
+        // Safety:
+        // ASCII is always valid UTF-8
+        Ok(unsafe { InlineStr::from_utf8_unchecked(bytes) })
     }
-}
 
-impl From<&String> for InlineStr {
-    fn from(value: &String) -> Self {
-        Self {
-            inner: InlineArray::from(value.as_bytes()),
+    /// Checks that the string holds valid UTF-8.
+    ///
+    /// This always holds for strings created through safe code, it's meant as a safety net for
+    /// values built with [`InlineStr::from_utf8_unchecked`]. With the `debug-validate` feature,
+    /// the same check runs on every access in debug builds.
+    pub fn validate(&self) -> Result<(), Utf8Error> {
+        from_utf8(&self.inner).map(|_| ())
+    }
+
+    /// Converts an `OsStr` into a string, replacing invalid UTF-8 with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    #[cfg(feature = "std")]
+    pub fn from_os_str_lossy(value: &OsStr) -> InlineStr {
+        match value.to_string_lossy() {
+            Cow::Borrowed(s) => InlineStr::from(s),
+            Cow::Owned(s) => InlineStr::from(s),
         }
     }
-}
 
-impl From<&str> for InlineStr {
-    fn from(value: &str) -> Self {
-        Self {
-            inner: InlineArray::from(value.as_bytes()),
+    /// Converts a `Path` into a string, replacing invalid UTF-8 with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    #[cfg(feature = "std")]
+    pub fn from_path_lossy(value: &Path) -> InlineStr {
+        InlineStr::from_os_str_lossy(value.as_os_str())
+    }
+
+    /// Returns the string as an [`OsStr`], without copying.
+    #[cfg(feature = "std")]
+    pub fn as_os_str(&self) -> &OsStr {
+        OsStr::new(self.as_str())
+    }
+
+    /// Returns the string as a [`Path`], without copying.
+    #[cfg(feature = "std")]
+    pub fn as_path(&self) -> &Path {
+        Path::new(self.as_str())
+    }
+
+    /// Returns `true` if all bytes of the string are ASCII, checking the bytes directly.
+    pub fn is_ascii(&self) -> bool {
+        self.as_bytes().is_ascii()
+    }
+
+    /// Returns `true` if `index` is the start or end of a char, like [`str::is_char_boundary`].
+    pub fn is_char_boundary(&self, index: usize) -> bool {
+        str::is_char_boundary(self, index)
+    }
+
+    /// Returns the closest char boundary at or below `index`, or the string's length if `index`
+    /// is past its end.
+    pub fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
         }
+
+        // A char is at most 4 bytes long, so a boundary is at most 3 bytes away
+        (index.saturating_sub(3)..=index)
+            .rev()
+            .find(|&idx| self.is_char_boundary(idx))
+            .unwrap_or(0)
     }
-}
 
-impl Deref for InlineStr {
-    type Target = str;
+    /// The empty string.
+    pub const EMPTY: InlineStr = InlineStr {
+        inner: inline_array::EMPTY,
+    };
 
-    fn deref(&self) -> &Self::Target {
-        // Safety:
-        // InlineStr can only be created from valid UTF8 byte sequences
-        unsafe { str::from_utf8_unchecked(&self.inner) }
+    /// The longest string, in bytes, that is stored inline.
+    ///
+    /// This follows the layout of `inline-array`, so it may change with the underlying crate
+    /// or the target, and isn't covered by semver guarantees.
+    pub const INLINE_CAPACITY: usize = size_of::<InlineArray>() - 1;
+
+    /// Returns `true` if a string of `len_bytes` bytes would be stored inline.
+    ///
+    /// ```
+    /// # use inline_str::InlineStr;
+    /// const MAX_KEY_LEN: usize = 6;
+    /// const _: () = assert!(InlineStr::will_inline(MAX_KEY_LEN));
+    /// ```
+    pub const fn will_inline(len_bytes: usize) -> bool {
+        len_bytes <= Self::INLINE_CAPACITY
+    }
+
+    /// Creates an inline string, failing instead of allocating if `s` doesn't fit.
+    ///
+    /// ```
+    /// # use inline_str::InlineStr;
+    /// assert!(InlineStr::try_inline("key").unwrap().is_inline());
+    /// assert_eq!(InlineStr::try_inline("a much longer key").unwrap_err().attempted_len(), 17);
+    /// ```
+    pub fn try_inline(s: &str) -> Result<InlineStr, CapacityError> {
+        if Self::will_inline(s.len()) {
+            Ok(InlineStr::from(s))
+        } else {
+            Err(CapacityError::new(s.len()))
+        }
+    }
+
+    /// Returns `true` if the string is stored inline, without a heap allocation.
+    ///
+    /// Which strings are stored inline depends on `inline-array` and the target, and isn't
+    /// covered by semver guarantees.
+    pub fn is_inline(&self) -> bool {
+        // Inline bytes live inside the `InlineArray` itself, heap bytes live elsewhere
+        let start = (&raw const self.inner).addr();
+        (start..start + size_of::<InlineArray>()).contains(&self.inner.as_ptr().addr())
+    }
+
+    /// Returns `true` if the string is stored in a (possibly shared) heap allocation.
+    ///
+    /// This is always the opposite of [`InlineStr::is_inline`].
+    pub fn is_heap_allocated(&self) -> bool {
+        !self.is_inline()
+    }
+
+    /// Returns a mutable reference to the string, in the spirit of [`Arc::make_mut`].
+    ///
+    /// Inline strings are edited in place. Heap-allocated strings are currently always copied into
+    /// a new allocation first, as this crate can't tell whether their buffer is shared with other
+    /// clones, and `InlineArray::make_mut` doesn't unshare it.
+    pub fn make_mut(&mut self) -> &mut str {
+        if self.is_heap_allocated() {
+            self.inner = new_inner(self.as_bytes());
+        }
+        // Safety: the bytes are valid UTF-8, and `&mut str` only allows edits that keep them valid
+        unsafe { str::from_utf8_unchecked_mut(self.inner.make_mut()) }
+    }
+
+    /// Converts the string to ASCII lower case in place, see [`str::make_ascii_lowercase`].
+    ///
+    /// Like [`InlineStr::make_mut`], this copies heap-allocated strings.
+    pub fn make_ascii_lowercase(&mut self) {
+        self.make_mut().make_ascii_lowercase();
+    }
+
+    /// Converts the string to ASCII upper case in place, see [`str::make_ascii_uppercase`].
+    ///
+    /// Like [`InlineStr::make_mut`], this copies heap-allocated strings.
+    pub fn make_ascii_uppercase(&mut self) {
+        self.make_mut().make_ascii_uppercase();
+    }
+
+    /// Returns the string converted to ASCII lower case, reusing `self` if it has no ASCII upper
+    /// case chars. Otherwise this copies heap-allocated strings, like [`InlineStr::make_mut`].
+    pub fn ascii_lowercased(mut self) -> InlineStr {
+        if self.bytes().any(|b| b.is_ascii_uppercase()) {
+            self.make_ascii_lowercase();
+        }
+        self
+    }
+
+    /// Returns the string converted to ASCII upper case, reusing `self` if it has no ASCII lower
+    /// case chars. Otherwise this copies heap-allocated strings, like [`InlineStr::make_mut`].
+    pub fn ascii_uppercased(mut self) -> InlineStr {
+        if self.bytes().any(|b| b.is_ascii_lowercase()) {
+            self.make_ascii_uppercase();
+        }
+        self
+    }
+
+    /// Returns `true` if both strings share the same heap allocation, like [`Arc::ptr_eq`].
+    ///
+    /// Inline strings don't have an identity of their own, so two inline strings are considered
+    /// the same if their bytes are equal. A `true` result always implies the strings are equal.
+    pub fn ptr_eq(&self, other: &InlineStr) -> bool {
+        match (self.is_inline(), other.is_inline()) {
+            (true, true) => self.as_bytes() == other.as_bytes(),
+            (false, false) => self.as_ptr() == other.as_ptr(),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of heap bytes allocated for this string's contents, including
+    /// `inline-array`'s bookkeeping, or 0 if it is stored inline.
+    ///
+    /// Clones share their heap buffer, and each of them reports its full size, so summing this
+    /// over clones of the same string over-counts.
+    pub fn heap_size(&self) -> usize {
+        heap_size_of(self.len())
+    }
+
+    /// Returns the size of this value plus [`InlineStr::heap_size`].
+    pub fn total_size(&self) -> usize {
+        size_of::<Self>() + self.heap_size()
+    }
+
+    /// Returns the 64-bit FNV-1a hash of the string's bytes.
+    ///
+    /// Unlike [`Hash`](core::hash::Hash), the result is stable across program runs, platforms and
+    /// versions of this crate, so it can be persisted or used to route keys between processes.
+    pub fn stable_hash(&self) -> u64 {
+        self.stable_hash_with_seed(0)
+    }
+
+    /// Returns the 64-bit FNV-1a hash of the string's bytes, with `seed` mixed into the offset
+    /// basis. A seed of 0 gives the same result as [`InlineStr::stable_hash`].
+    pub fn stable_hash_with_seed(&self, seed: u64) -> u64 {
+        fnv1a(self.as_bytes(), seed)
+    }
+
+    /// Compares two strings by their Unicode scalar values.
+    ///
+    /// This is always identical to [`Ord::cmp`], as UTF-8 byte order matches code point order,
+    /// and exists to make that intent explicit at call sites.
+    pub fn cmp_codepoints(&self, other: &InlineStr) -> Ordering {
+        self.cmp(other)
+    }
+
+    /// Returns an iterator over the lines of the string as owned [`InlineStr`]s, with the same
+    /// semantics as [`str::lines`].
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = InlineStr> + '_ {
+        str::lines(self).map(InlineStr::from)
+    }
+
+    /// Splits the string by Unicode whitespace into owned [`InlineStr`]s, with the same semantics
+    /// as [`str::split_whitespace`].
+    pub fn split_whitespace(&self) -> impl DoubleEndedIterator<Item = InlineStr> + '_ {
+        str::split_whitespace(self).map(InlineStr::from)
+    }
+
+    /// Splits the string by ASCII whitespace into owned [`InlineStr`]s, with the same semantics
+    /// as [`str::split_ascii_whitespace`].
+    pub fn split_ascii_whitespace(&self) -> impl DoubleEndedIterator<Item = InlineStr> + '_ {
+        str::split_ascii_whitespace(self).map(InlineStr::from)
+    }
+
+    /// Returns the rest of the string as an owned [`InlineStr`] if it starts with `prefix`,
+    /// with the same semantics as [`str::strip_prefix`].
+    ///
+    /// To borrow the rest of the string instead, use `self.as_str().strip_prefix(prefix)`.
+    #[doc(alias = "stripped_prefix")]
+    pub fn strip_prefix<P: Pattern>(&self, prefix: P) -> Option<InlineStr> {
+        prefix.strip_prefix_of(self).map(InlineStr::from)
+    }
+
+    /// Returns the rest of the string as an owned [`InlineStr`] if it ends with `suffix`,
+    /// with the same semantics as [`str::strip_suffix`].
+    ///
+    /// To borrow the rest of the string instead, use `self.as_str().strip_suffix(suffix)`.
+    pub fn strip_suffix<P: Pattern>(&self, suffix: P) -> Option<InlineStr> {
+        suffix.strip_suffix_of(self).map(InlineStr::from)
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat` as owned [`InlineStr`]s,
+    /// with the same semantics as [`str::matches`].
+    #[doc(alias = "matches_inline")]
+    pub fn matches<'a, P: Pattern + 'a>(&'a self, pat: P) -> impl Iterator<Item = InlineStr> + 'a {
+        pat.match_indices_of(self).map(|(_, m)| InlineStr::from(m))
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pat` as owned [`InlineStr`]s,
+    /// along with their byte offset in this string, with the same semantics as [`str::match_indices`].
+    pub fn match_indices<'a, P: Pattern + 'a>(
+        &'a self,
+        pat: P,
+    ) -> impl Iterator<Item = (usize, InlineStr)> + 'a {
+        pat.match_indices_of(self)
+            .map(|(idx, m)| (idx, InlineStr::from(m)))
+    }
+
+    /// Returns an iterator over the substrings separated by `pat` as owned [`InlineStr`]s,
+    /// in reverse order, with the same semantics as [`str::rsplit`].
+    pub fn rsplit_to_inline<'a, P: Pattern + 'a>(
+        &'a self,
+        pat: P,
+    ) -> impl Iterator<Item = InlineStr> + 'a {
+        pat.rsplit_of(self).map(InlineStr::from)
+    }
+
+    /// Splits the string by `pat` into an array of at most `N` owned [`InlineStr`]s, without
+    /// allocating a `Vec`, returning the array and the number of pieces.
+    ///
+    /// Like [`str::splitn`], if there are more than `N` pieces the last one holds the rest of the
+    /// string, unsplit. Unused slots are left empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub fn split_collect<const N: usize, P: Pattern>(&self, pat: P) -> ([InlineStr; N], usize) {
+        assert!(N > 0, "array length must be non-zero");
+        let mut pieces = pat.splitn_of(N, self);
+        let mut count = 0;
+        let array = core::array::from_fn(|_| match pieces.next() {
+            Some(piece) => {
+                count += 1;
+                InlineStr::from(piece)
+            }
+            None => InlineStr::EMPTY,
+        });
+        (array, count)
+    }
+
+    /// Returns the substring between the char indices in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or extends past the number of chars in the string, like
+    /// byte-indexed slicing does.
+    pub fn slice_chars(&self, range: impl RangeBounds<usize>) -> InlineStr {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        match self.get_chars(bounds) {
+            Some(sliced) => sliced,
+            None => panic!(
+                "char range {bounds:?} is out of bounds of a string with {} chars",
+                self.chars().count()
+            ),
+        }
+    }
+
+    /// Returns the substring between the char indices in `range`, or `None` if the range is
+    /// decreasing or extends past the number of chars in the string.
+    pub fn get_chars(&self, range: impl RangeBounds<usize>) -> Option<InlineStr> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => Some(end.checked_add(1)?),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => None,
+        };
+
+        if end.is_some_and(|end| start > end) {
+            return None;
+        }
+
+        let start_byte = match start {
+            0 => 0,
+            start => char_to_byte(self, start)?,
+        };
+        let end_byte = match end {
+            Some(end) => start_byte + char_to_byte(&self[start_byte..], end - start)?,
+            None if start_byte == 0 => return Some(self.clone()),
+            None => self.len(),
+        };
+
+        Some(InlineStr::from(&self[start_byte..end_byte]))
+    }
+
+    /// Returns a copy of the string with `s` inserted at byte index `idx`, like [`String::insert_str`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds or not on a char boundary.
+    pub fn inserted(&self, idx: usize, s: &str) -> InlineStr {
+        self.spliced(idx..idx, s)
+    }
+
+    /// Returns the string with `s` appended, reusing `self` if `s` is empty.
+    pub fn with_appended(self, s: &str) -> InlineStr {
+        if s.is_empty() {
+            return self;
+        }
+        self.inserted(self.len(), s)
+    }
+
+    /// Returns the string with `s` prepended, reusing `self` if `s` is empty.
+    pub fn with_prepended(self, s: &str) -> InlineStr {
+        if s.is_empty() {
+            return self;
+        }
+        self.inserted(0, s)
+    }
+
+    /// Returns a copy of the string with the bytes in `range` removed, like [`String::drain`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its ends are not on char boundaries.
+    pub fn removed_range(&self, range: impl RangeBounds<usize>) -> InlineStr {
+        self.spliced(range, "")
+    }
+
+    /// Returns a copy of the string with the bytes in `range` replaced by `replacement`, like
+    /// [`String::replace_range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or its ends are not on char boundaries.
+    pub fn spliced(&self, range: impl RangeBounds<usize>, replacement: &str) -> InlineStr {
+        // Slicing checks the range, with the same panic messages as `str`
+        let removed = &self[(range.start_bound().cloned(), range.end_bound().cloned())];
+        let start = removed.as_ptr().addr() - self.as_ptr().addr();
+        let end = start + removed.len();
+
+        let mut builder = Builder::with_capacity(self.len() - removed.len() + replacement.len());
+        builder.push_str(&self[..start]);
+        builder.push_str(replacement);
+        builder.push_str(&self[end..]);
+        builder.finish()
+    }
+
+    /// Returns the string with every `\r\n` and lone `\r` converted to `\n`.
+    ///
+    /// If the string contains no `\r`, this returns a cheap clone of `self` without allocating.
+    pub fn normalize_newlines(&self) -> InlineStr {
+        self.normalize_newlines_to(LineEnding::Lf)
+    }
+
+    /// Returns the string with every line ending (`\n`, `\r\n` or a lone `\r`) converted to `ending`.
+    ///
+    /// If the string already only uses `ending`, this returns a cheap clone of `self` without allocating.
+    pub fn normalize_newlines_to(&self, ending: LineEnding) -> InlineStr {
+        if ending.is_normalized(self.as_bytes()) {
+            return self.clone();
+        }
+
+        let mut builder = Builder::with_capacity(self.len());
+        let mut rest = self.as_str();
+        while let Some(idx) = rest.find(['\r', '\n']) {
+            builder.push_str(&rest[..idx]);
+            builder.push_str(ending.as_str());
+
+            let ending_len = if rest[idx..].starts_with("\r\n") {
+                2
+            } else {
+                1
+            };
+            rest = &rest[idx + ending_len..];
+        }
+        builder.push_str(rest);
+        builder.finish()
+    }
+
+    /// Removes the longest common leading indentation from every line, like Python's
+    /// `textwrap.dedent`.
+    ///
+    /// Indentation is made of spaces and tabs, compared by exact prefix without expanding tabs.
+    /// Lines that only contain whitespace don't affect the common indentation, and are stripped of
+    /// as much of it as they contain. Line endings are preserved as-is.
+    ///
+    /// If there is no common indentation, this returns a cheap clone of `self`.
+    pub fn dedent(&self) -> InlineStr {
+        const INDENT: [char; 2] = [' ', '\t'];
+
+        let content_len = |line: &str| {
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            line.strip_suffix('\r').unwrap_or(line).len()
+        };
+        let indent_len = |line: &str| line.len() - line.trim_start_matches(INDENT).len();
+
+        let margin = str::split_inclusive(self, '\n')
+            .map(|line| &line[..content_len(line)])
+            .filter(|line| indent_len(line) != line.len())
+            .map(|line| &line[..indent_len(line)])
+            .reduce(|margin, indent| {
+                let common = margin
+                    .bytes()
+                    .zip(indent.bytes())
+                    .take_while(|(a, b)| a == b);
+                &margin[..common.count()]
+            })
+            .unwrap_or_default();
+
+        if margin.is_empty() {
+            return self.clone();
+        }
+
+        let mut builder = Builder::with_capacity(self.len());
+        for line in str::split_inclusive(self, '\n') {
+            match line.strip_prefix(margin) {
+                Some(dedented) => builder.push_str(dedented),
+                // Only whitespace-only lines can be indented less than the margin
+                None => builder.push_str(&line[indent_len(line)..]),
+            }
+        }
+        builder.finish()
+    }
+
+    /// Returns the longest prefix of the string that is at most `max_bytes` long, without
+    /// splitting a char.
+    ///
+    /// If the string already fits, this returns a cheap clone of `self`.
+    pub fn truncate(&self, max_bytes: usize) -> InlineStr {
+        if max_bytes >= self.len() {
+            return self.clone();
+        }
+
+        InlineStr::from(&self[..self.floor_char_boundary(max_bytes)])
+    }
+
+    /// Returns the string truncated to at most `max_chars` chars, ending with `ellipsis` if
+    /// anything was cut off.
+    ///
+    /// The ellipsis counts toward `max_chars`, unless it is longer than `max_chars` itself, in
+    /// which case the string is truncated without it. If the string already fits, this returns
+    /// a cheap clone of `self`.
+    pub fn truncate_display(&self, max_chars: usize, ellipsis: &str) -> InlineStr {
+        let end = match char_to_byte(self, max_chars) {
+            Some(end) if end < self.len() => end,
+            _ => return self.clone(),
+        };
+
+        let ellipsis_chars = ellipsis.chars().count();
+        if ellipsis_chars > max_chars {
+            return InlineStr::from(&self[..end]);
+        }
+
+        let end = char_to_byte(self, max_chars - ellipsis_chars)
+            .expect("the string has more than max_chars chars");
+        let mut builder = Builder::with_capacity(end + ellipsis.len());
+        builder.push_str(&self[..end]);
+        builder.push_str(ellipsis);
+        builder.finish()
+    }
+
+    /// Returns an iterator over chunks of `n` chars as owned [`InlineStr`]s. The last chunk
+    /// may be shorter than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0, like [`slice::chunks`].
+    pub fn char_chunks(&self, n: usize) -> impl Iterator<Item = InlineStr> + '_ {
+        assert!(n != 0, "chunk size must be non-zero");
+
+        let mut rest = self.as_str();
+        core::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            let end = rest
+                .char_indices()
+                .nth(n)
+                .map_or(rest.len(), |(idx, _)| idx);
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+            Some(InlineStr::from(chunk))
+        })
+    }
+
+    /// Returns the string with every char escaped by [`char::escape_debug`], identical to
+    /// collecting [`str::escape_debug`].
+    pub fn escape_debug(&self) -> InlineStr {
+        Self::from_display(str::escape_debug(self))
+    }
+
+    /// Returns the string with every char escaped by [`char::escape_default`], identical to
+    /// collecting [`str::escape_default`].
+    pub fn escape_default(&self) -> InlineStr {
+        Self::from_display(str::escape_default(self))
+    }
+
+    /// Returns the string with every char escaped by [`char::escape_unicode`], identical to
+    /// collecting [`str::escape_unicode`].
+    pub fn escape_unicode(&self) -> InlineStr {
+        Self::from_display(str::escape_unicode(self))
+    }
+
+    fn from_display(value: impl core::fmt::Display) -> InlineStr {
+        let mut builder = Builder::new();
+        write!(builder, "{value}").expect("Builder never fails to write");
+        builder.finish()
+    }
+
+    /// Returns a string with only the chars for which `f` returns `true`.
+    ///
+    /// If every char is retained, this returns a cheap clone of `self`.
+    pub fn retain_chars(&self, mut f: impl FnMut(char) -> bool) -> InlineStr {
+        let mut char_indices = self.char_indices();
+
+        let Some((first_removed, c)) = char_indices.find(|&(_, c)| !f(c)) else {
+            return self.clone();
+        };
+
+        let mut builder = Builder::new();
+        builder.push_str(&self[..first_removed]);
+        for c in self[first_removed + c.len_utf8()..]
+            .chars()
+            .filter(|&c| f(c))
+        {
+            builder.push(c);
+        }
+        builder.finish()
+    }
+
+    /// Returns a string with every char replaced by the result of `f`.
+    ///
+    /// If `f` returns every char unchanged, this returns a cheap clone of `self`.
+    pub fn map_chars(&self, mut f: impl FnMut(char) -> char) -> InlineStr {
+        let mut char_indices = self.char_indices();
+
+        let Some((first_changed, mapped)) = char_indices.find_map(|(idx, c)| {
+            let mapped = f(c);
+            (mapped != c).then_some((idx, mapped))
+        }) else {
+            return self.clone();
+        };
+
+        let mut builder = Builder::new();
+        builder.push_str(&self[..first_changed]);
+        builder.push(mapped);
+        for (_, c) in char_indices {
+            builder.push(f(c));
+        }
+        builder.finish()
+    }
+
+    /// Returns the string right-aligned to `width` chars, padded on the left with `fill`.
+    ///
+    /// If the string is already at least `width` chars long, this returns a cheap clone of `self`.
+    pub fn pad_start(&self, width: usize, fill: char) -> InlineStr {
+        self.padded(width, fill, |padding| padding)
+    }
+
+    /// Returns the string left-aligned to `width` chars, padded on the right with `fill`.
+    ///
+    /// If the string is already at least `width` chars long, this returns a cheap clone of `self`.
+    pub fn pad_end(&self, width: usize, fill: char) -> InlineStr {
+        self.padded(width, fill, |_| 0)
+    }
+
+    /// Returns the string centered in `width` chars, padded on both sides with `fill`. Like
+    /// `format!("{:^width$}")`, an odd amount of padding puts the extra `fill` on the right.
+    ///
+    /// If the string is already at least `width` chars long, this returns a cheap clone of `self`.
+    pub fn center(&self, width: usize, fill: char) -> InlineStr {
+        self.padded(width, fill, |padding| padding / 2)
+    }
+
+    fn padded(&self, width: usize, fill: char, left: impl FnOnce(usize) -> usize) -> InlineStr {
+        let char_count = self.chars().count();
+        if char_count >= width {
+            return self.clone();
+        }
+
+        let padding = width - char_count;
+        let left = left(padding);
+
+        let mut builder = Builder::new();
+        (0..left).for_each(|_| builder.push(fill));
+        builder.push_str(self);
+        (left..padding).for_each(|_| builder.push(fill));
+        builder.finish()
+    }
+
+    /// Returns the byte length of the longest common prefix of `self` and `other`, backed off
+    /// so it never ends in the middle of a char.
+    pub fn common_prefix_len(&self, other: &str) -> usize {
+        let len = self
+            .bytes()
+            .zip(other.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        // Both strings share the same bytes up to `len`, so a boundary in one is a boundary in the other.
+        self.floor_char_boundary(len)
+    }
+
+    /// Returns the byte length of the longest common suffix of `self` and `other`, backed off
+    /// so it never starts in the middle of a char.
+    pub fn common_suffix_len(&self, other: &str) -> usize {
+        let len = self
+            .bytes()
+            .rev()
+            .zip(other.bytes().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut start = self.len() - len;
+        while !self.is_char_boundary(start) {
+            start += 1;
+        }
+
+        self.len() - start
+    }
+
+    /// Returns the longest common prefix of `self` and `other`.
+    pub fn common_prefix(&self, other: &str) -> InlineStr {
+        InlineStr::from(&self[..self.common_prefix_len(other)])
+    }
+
+    /// Returns the longest common suffix of `self` and `other`.
+    pub fn common_suffix(&self, other: &str) -> InlineStr {
+        InlineStr::from(&self[self.len() - self.common_suffix_len(other)..])
+    }
+
+    /// Copies the string into a [`CString`], failing if it contains an interior NUL byte.
+    pub fn to_cstring(&self) -> Result<CString, NulError> {
+        CString::new(self.as_bytes())
+    }
+
+    /// Splits the string on the first occurrence of `delimiter` into two owned [`InlineStr`]s,
+    /// with the same semantics as [`str::split_once`].
+    pub fn split_once<P: Pattern>(&self, delimiter: P) -> Option<(InlineStr, InlineStr)> {
+        delimiter
+            .split_once_of(self)
+            .map(|(a, b)| (InlineStr::from(a), InlineStr::from(b)))
+    }
+
+    /// Splits the string on the last occurrence of `delimiter` into two owned [`InlineStr`]s,
+    /// with the same semantics as [`str::rsplit_once`].
+    pub fn rsplit_once<P: Pattern>(&self, delimiter: P) -> Option<(InlineStr, InlineStr)> {
+        delimiter
+            .rsplit_once_of(self)
+            .map(|(a, b)| (InlineStr::from(a), InlineStr::from(b)))
+    }
+
+    /// Returns the string with its `char`s in reverse order.
+    ///
+    /// This reverses Unicode scalar values, not grapheme clusters, so combining characters end
+    /// up attached to the wrong base character, and multi-scalar emoji are split apart.
+    pub fn reversed(&self) -> InlineStr {
+        let mut builder = Builder::with_capacity(self.len());
+        for c in self.chars().rev() {
+            builder.push(c);
+        }
+        builder.finish()
+    }
+
+    /// Splits the string at the byte index `mid` into two owned [`InlineStr`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is past the end of the string or not on a char boundary, like
+    /// [`str::split_at`].
+    pub fn split_at_inline(&self, mid: usize) -> (InlineStr, InlineStr) {
+        let (a, b) = self.split_at(mid);
+        (InlineStr::from(a), InlineStr::from(b))
     }
 }
 
-impl PartialEq<String> for InlineStr {
-    fn eq(&self, other: &String) -> bool {
-        (**self).eq(other)
+/// A line ending convention, see [`InlineStr::normalize_newlines_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, used on Unix-like systems.
+    Lf,
+    /// `\r\n`, used on Windows.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the line ending as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    fn is_normalized(self, bytes: &[u8]) -> bool {
+        match self {
+            LineEnding::Lf => !bytes.contains(&b'\r'),
+            LineEnding::CrLf => bytes.iter().enumerate().all(|(idx, b)| match b {
+                b'\r' => bytes.get(idx + 1) == Some(&b'\n'),
+                b'\n' => idx > 0 && bytes[idx - 1] == b'\r',
+                _ => true,
+            }),
+        }
     }
 }
 
-impl PartialEq<InlineStr> for String {
-    fn eq(&self, other: &InlineStr) -> bool {
-        other.eq(self)
+const _: () = assert!(size_of::<InlineStr>() == 8);
+
+/// Returns the number of bytes the UTF-8 encoding of `chars` takes.
+///
+/// Together with [`InlineStr::will_inline`], this tells whether a string built from `chars`
+/// will be stored inline before building it.
+///
+/// ```
+/// # use inline_str::{utf8_len_of_chars, InlineStr};
+/// assert_eq!(utf8_len_of_chars("héllo".chars()), 6);
+/// assert!(InlineStr::will_inline(utf8_len_of_chars(['a', 'é', '€'])));
+/// ```
+pub fn utf8_len_of_chars<I: IntoIterator<Item = char>>(chars: I) -> usize {
+    chars.into_iter().map(char::len_utf8).sum()
+}
+
+/// Creates the storage for a new string, every constructor goes through here.
+#[inline(always)]
+fn new_inner(bytes: &[u8]) -> InlineArray {
+    #[cfg(feature = "stats")]
+    stats::record(bytes.len());
+
+    InlineArray::from(bytes)
+}
+
+/// The heap bytes `inline-array` allocates for a string of `len` bytes.
+pub(crate) const fn heap_size_of(len: usize) -> usize {
+    match len {
+        _ if InlineStr::will_inline(len) => 0,
+        ..=255 => len + 2,
+        _ => len + 8,
     }
 }
 
-impl<'a> PartialEq<&'a str> for InlineStr {
-    fn eq(&self, other: &&'a str) -> bool {
-        (&&**self).eq(other)
+/// The 64-bit FNV-1a hash of `bytes`, with `seed` mixed into the offset basis.
+pub(crate) fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS ^ seed, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Validates `bytes` as UTF-8, the single entry point used by every fallible conversion in the crate.
+pub(crate) fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    #[cfg(feature = "simdutf8")]
+    {
+        // The fast path's error carries no position, so failures are re-validated to get one.
+        simdutf8::basic::from_utf8(bytes).or_else(|_| str::from_utf8(bytes))
+    }
+
+    #[cfg(not(feature = "simdutf8"))]
+    {
+        str::from_utf8(bytes)
     }
 }
 
-impl PartialEq<InlineStr> for &str {
-    fn eq(&self, other: &InlineStr) -> bool {
-        other.eq(self)
+/// Returns the byte offset of the `n`th char in `s`, or its length if it has exactly `n` chars.
+fn char_to_byte(s: &str, n: usize) -> Option<usize> {
+    s.char_indices()
+        .map(|(idx, _)| idx)
+        .chain(core::iter::once(s.len()))
+        .nth(n)
+}
+
+impl core::fmt::Display for InlineStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&**self, f)
     }
 }
 
-impl PartialEq<Cow<'_, str>> for InlineStr {
-    fn eq(&self, other: &Cow<'_, str>) -> bool {
-        (**self).eq(other)
+impl core::fmt::Debug for InlineStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
     }
 }
 
-impl PartialEq<InlineStr> for Cow<'_, str> {
-    fn eq(&self, other: &InlineStr) -> bool {
-        other.eq(self)
+/// Formats the UTF-8 bytes of the string as lowercase hex, two digits per byte.
+impl core::fmt::LowerHex for InlineStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|b| write!(f, "{b:02x}"))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::hash::{BuildHasher, RandomState};
+/// Formats the UTF-8 bytes of the string as uppercase hex, two digits per byte.
+impl core::fmt::UpperHex for InlineStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|b| write!(f, "{b:02X}"))
+    }
+}
 
-    use super::*;
+impl core::hash::Hash for InlineStr {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Getting the `&str` doesn't validate anything, and `str::hash` already writes the raw
+        // bytes, so hashing the bytes by hand isn't faster (see `benches/hash.rs`). Delegating
+        // also keeps `Borrow<str>` lookups correct for hashers that specialize `write_str`.
+        let as_str: &str = self;
+        as_str.hash(state);
+    }
+}
 
-    #[test]
-    fn test_basic_eq() {
-        let words = "the quick brown fox";
-        let inline_words = InlineStr::from(words);
+impl PartialEq for InlineStr {
+    fn eq(&self, other: &Self) -> bool {
+        bytes_eq(&self.inner, &other.inner)
+    }
+}
 
-        assert_eq!(words, &*inline_words);
-        assert_eq!(words, inline_words);
-        assert_eq!(inline_words, words);
+#[inline]
+fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
 
-    #[test]
-    fn test_basic_hash() {
-        let hasher = RandomState::new();
+    // Clones of a heap-backed string share the same buffer, no need to look at the bytes.
+    if a.as_ptr() == b.as_ptr() {
+        return true;
+    }
 
-        let words = "the quick brown fox";
-        let inline_words = InlineStr::from(words);
+    a == b
+}
 
-        let words_hash = hasher.hash_one(words);
+impl From<String> for InlineStr {
+    fn from(value: String) -> Self {
+        Self {
+            inner: new_inner(value.as_bytes()),
+        }
+    }
+}
+
+impl From<&String> for InlineStr {
+    fn from(value: &String) -> Self {
+        Self {
+            inner: new_inner(value.as_bytes()),
+        }
+    }
+}
+
+impl From<&str> for InlineStr {
+    fn from(value: &str) -> Self {
+        Self {
+            inner: new_inner(value.as_bytes()),
+        }
+    }
+}
+
+impl From<InlineStr> for Vec<u8> {
+    fn from(value: InlineStr) -> Self {
+        value.as_bytes().to_vec()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<InlineStr> for OsString {
+    fn from(value: InlineStr) -> Self {
+        OsString::from(value.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<InlineStr> for PathBuf {
+    fn from(value: InlineStr) -> Self {
+        PathBuf::from(value.as_str())
+    }
+}
+
+macro_rules! impl_from_iter {
+    ($([$($generics:tt)*] $ty:ty => |$builder:ident, $item:ident| $push:expr),* $(,)?) => {
+        $(
+            impl<$($generics)*> FromIterator<$ty> for InlineStr {
+                fn from_iter<I: IntoIterator<Item = $ty>>(iter: I) -> Self {
+                    let mut $builder = Builder::new();
+                    for $item in iter {
+                        $push;
+                    }
+                    $builder.finish()
+                }
+            }
+        )*
+    };
+}
+
+// Collected on the stack, so short results never allocate
+impl_from_iter!(
+    [] char => |builder, c| builder.push(c),
+    ['a] &'a char => |builder, c| builder.push(*c),
+    ['a] &'a str => |builder, s| builder.push_str(s),
+    [] String => |builder, s| builder.push_str(&s),
+    ['a] Cow<'a, str> => |builder, s| builder.push_str(&s),
+    [] InlineStr => |builder, s| builder.push_str(&s),
+);
+
+impl TryFrom<&CStr> for InlineStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: &CStr) -> Result<Self, Self::Error> {
+        from_utf8(value.to_bytes()).map(InlineStr::from)
+    }
+}
+
+impl str::FromStr for InlineStr {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(InlineStr::from(s))
+    }
+}
+
+impl TryFrom<&[u8]> for InlineStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        from_utf8(value).map(InlineStr::from)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Fails if the string isn't valid UTF-8, see [`InlineStr::from_os_str_lossy`] for a lossy
+/// conversion.
+impl TryFrom<&OsStr> for InlineStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: &OsStr) -> Result<Self, Self::Error> {
+        from_utf8(value.as_encoded_bytes()).map(InlineStr::from)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Fails if the path isn't valid UTF-8, see [`InlineStr::from_path_lossy`] for a lossy
+/// conversion.
+impl TryFrom<&Path> for InlineStr {
+    type Error = Utf8Error;
+
+    fn try_from(value: &Path) -> Result<Self, Self::Error> {
+        InlineStr::try_from(value.as_os_str())
+    }
+}
+
+impl Borrow<str> for InlineStr {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+// `Borrow<InlineStr>` comes from the blanket `Borrow<T> for T` impl
+impl AsRef<InlineStr> for InlineStr {
+    fn as_ref(&self) -> &InlineStr {
+        self
+    }
+}
+
+impl Deref for InlineStr {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        if let Err(err) = self.validate() {
+            panic!("InlineStr holds invalid UTF-8: {err}");
+        }
+
+        // Safety:
+        // InlineStr can only be created from valid UTF8 byte sequences
+        unsafe { str::from_utf8_unchecked(&self.inner) }
+    }
+}
+
+macro_rules! impl_index {
+    ($($range:ty),* $(,)?) => {
+        $(
+            impl Index<$range> for InlineStr {
+                type Output = str;
+
+                fn index(&self, index: $range) -> &Self::Output {
+                    &self.as_str()[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_index!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>,
+    (Bound<usize>, Bound<usize>),
+);
+
+impl<'a> PartialEq<&'a str> for InlineStr {
+    fn eq(&self, other: &&'a str) -> bool {
+        (&&**self).eq(other)
+    }
+}
+
+impl PartialEq<InlineStr> for &str {
+    fn eq(&self, other: &InlineStr) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<str> for InlineStr {
+    fn eq(&self, other: &str) -> bool {
+        (**self).eq(other)
+    }
+}
+
+impl PartialEq<InlineStr> for str {
+    fn eq(&self, other: &InlineStr) -> bool {
+        other.eq(self)
+    }
+}
+
+/// A string equals a `char` if it consists of exactly that one character.
+impl PartialEq<char> for InlineStr {
+    fn eq(&self, other: &char) -> bool {
+        self.as_bytes() == other.encode_utf8(&mut [0; 4]).as_bytes()
+    }
+}
+
+impl PartialEq<InlineStr> for char {
+    fn eq(&self, other: &InlineStr) -> bool {
+        other.eq(self)
+    }
+}
+
+macro_rules! impl_eq_owned {
+    ($([$($generics:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> PartialEq<$ty> for InlineStr {
+                fn eq(&self, other: &$ty) -> bool {
+                    (**self).eq(&**other)
+                }
+            }
+
+            impl<$($generics)*> PartialEq<InlineStr> for $ty {
+                fn eq(&self, other: &InlineStr) -> bool {
+                    other.eq(self)
+                }
+            }
+
+            impl<$($generics)*> PartialEq<$ty> for &InlineStr {
+                fn eq(&self, other: &$ty) -> bool {
+                    (**self).eq(other)
+                }
+            }
+
+            impl<$($generics)*> PartialEq<&InlineStr> for $ty {
+                fn eq(&self, other: &&InlineStr) -> bool {
+                    (**other).eq(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_eq_owned!(
+    [] String,
+    ['a] Cow<'a, str>,
+    [] Box<str>,
+    [] Rc<str>,
+    [] Arc<str>,
+);
+
+macro_rules! impl_eq_bytes {
+    ($([$($generics:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> PartialEq<$ty> for InlineStr {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.as_bytes() == &other[..]
+                }
+            }
+
+            impl<$($generics)*> PartialEq<InlineStr> for $ty {
+                fn eq(&self, other: &InlineStr) -> bool {
+                    other.eq(self)
+                }
+            }
+        )*
+    };
+}
+
+// An `InlineStr` is always valid UTF-8, so comparing bytes is the same as comparing strings
+impl_eq_bytes!(
+    [] [u8],
+    ['a] &'a [u8],
+    [const N: usize] [u8; N],
+    ['a, const N: usize] &'a [u8; N],
+    [] Vec<u8>,
+);
+
+macro_rules! impl_ord_str {
+    ($([$($generics:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> PartialOrd<$ty> for InlineStr {
+                fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                    Some(self.as_str().cmp(&**other))
+                }
+            }
+
+            impl<$($generics)*> PartialOrd<InlineStr> for $ty {
+                fn partial_cmp(&self, other: &InlineStr) -> Option<Ordering> {
+                    Some((**self).cmp(other.as_str()))
+                }
+            }
+        )*
+    };
+}
+
+impl_ord_str!(['a] &'a str, [] String, ['a] Cow<'a, str>);
+
+impl PartialOrd<str> for InlineStr {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        Some(self.as_str().cmp(other))
+    }
+}
+
+impl PartialOrd<InlineStr> for str {
+    fn partial_cmp(&self, other: &InlineStr) -> Option<Ordering> {
+        Some(self.cmp(other.as_str()))
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! impl_cmp_as {
+    ($as:ident: $([$($generics:tt)*] $ty:ty),* $(,)?) => {
+        $(
+            impl<$($generics)*> PartialEq<$ty> for InlineStr {
+                fn eq(&self, other: &$ty) -> bool {
+                    $as::new(self.as_str()).eq(AsRef::<$as>::as_ref(other))
+                }
+            }
+
+            impl<$($generics)*> PartialEq<InlineStr> for $ty {
+                fn eq(&self, other: &InlineStr) -> bool {
+                    other.eq(self)
+                }
+            }
+
+            impl<$($generics)*> PartialOrd<$ty> for InlineStr {
+                fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                    $as::new(self.as_str()).partial_cmp(AsRef::<$as>::as_ref(other))
+                }
+            }
+
+            impl<$($generics)*> PartialOrd<InlineStr> for $ty {
+                fn partial_cmp(&self, other: &InlineStr) -> Option<Ordering> {
+                    other.partial_cmp(self).map(Ordering::reverse)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "std")]
+impl_cmp_as!(OsStr: [] OsStr, ['a] &'a OsStr, [] OsString);
+#[cfg(feature = "std")]
+impl_cmp_as!(Path: [] Path, ['a] &'a Path, [] PathBuf);
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        collections::{BTreeSet, HashMap},
+        hash::{BuildHasher, RandomState},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_basic_eq() {
+        let words = "the quick brown fox";
+        let inline_words = InlineStr::from(words);
+
+        assert_eq!(words, &*inline_words);
+        assert_eq!(words, inline_words);
+        assert_eq!(inline_words, words);
+    }
+
+    #[test]
+    fn test_owned_eq() {
+        let words = "the quick brown fox";
+        let inline_words = InlineStr::from(words);
+
+        let string = String::from(words);
+        let cow: Cow<'_, str> = Cow::Borrowed(words);
+        let boxed: Box<str> = Box::from(words);
+        let rc: Rc<str> = Rc::from(words);
+        let arc: Arc<str> = Arc::from(words);
+
+        assert_eq!(string, inline_words);
+        assert_eq!(inline_words, string);
+        assert_eq!(&inline_words, string);
+        assert_eq!(string, &inline_words);
+        assert_eq!(cow, inline_words);
+        assert_eq!(inline_words, cow);
+        assert_eq!(&inline_words, cow);
+        assert_eq!(cow, &inline_words);
+        assert_eq!(boxed, inline_words);
+        assert_eq!(inline_words, boxed);
+        assert_eq!(&inline_words, boxed);
+        assert_eq!(boxed, &inline_words);
+        assert_eq!(rc, inline_words);
+        assert_eq!(inline_words, rc);
+        assert_eq!(&inline_words, rc);
+        assert_eq!(rc, &inline_words);
+        assert_eq!(arc, inline_words);
+        assert_eq!(inline_words, arc);
+        assert_eq!(&inline_words, arc);
+        assert_eq!(arc, &inline_words);
+
+        assert_eq!(&inline_words, words);
+        assert_eq!(words, &inline_words);
+        assert_eq!(*words, inline_words);
+        assert_ne!(Arc::<str>::from("the lazy dog"), inline_words);
+    }
+
+    #[test]
+    fn test_basic_hash() {
+        let hasher = RandomState::new();
+
+        let words = "the quick brown fox";
+        let inline_words = InlineStr::from(words);
+
+        let words_hash = hasher.hash_one(words);
         let words_hash_2 = hasher.hash_one(words);
         let inline_hash = hasher.hash_one(inline_words);
 
         assert_eq!(words_hash, words_hash_2);
         assert_eq!(words_hash, inline_hash);
     }
+
+    #[test]
+    fn test_lines() {
+        let collect = |s: &str| InlineStr::from(s).lines().collect::<Vec<_>>();
+
+        assert_eq!(collect("one\r\ntwo\r\nthree"), ["one", "two", "three"]);
+        assert_eq!(collect("a\rb\nc\r\n"), ["a\rb", "c"]);
+        assert_eq!(collect("trailing\n"), ["trailing"]);
+        assert!(collect("").is_empty());
+        assert_eq!(collect("\n"), [""]);
+        assert_eq!(collect("\n\n"), ["", ""]);
+
+        for input in ["one\r\ntwo\r\nthree", "a\rb\nc\r\n", "", "\n", "x\n\ny\r"] {
+            let expected: Vec<&str> = input.lines().collect();
+            assert_eq!(collect(input), expected);
+
+            let expected_rev: Vec<&str> = input.lines().rev().collect();
+            let rev: Vec<InlineStr> = InlineStr::from(input).lines().rev().collect();
+            assert_eq!(rev, expected_rev);
+        }
+    }
+
+    #[test]
+    fn test_split_whitespace() {
+        let words = InlineStr::from("  move\t3 \n north  ");
+        assert_eq!(
+            words.split_whitespace().collect::<Vec<_>>(),
+            ["move", "3", "north"]
+        );
+        assert_eq!(
+            words.split_ascii_whitespace().collect::<Vec<_>>(),
+            ["move", "3", "north"]
+        );
+
+        let nbsp = InlineStr::from("a\u{a0}b c");
+        assert_eq!(nbsp.split_whitespace().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(
+            nbsp.split_ascii_whitespace().collect::<Vec<_>>(),
+            ["a\u{a0}b", "c"]
+        );
+
+        let blank = InlineStr::from(" \t\n\u{a0} ");
+        assert_eq!(blank.split_whitespace().count(), 0);
+        assert_eq!(
+            blank.split_ascii_whitespace().collect::<Vec<_>>(),
+            ["\u{a0}"]
+        );
+        assert_eq!(InlineStr::from("").split_whitespace().count(), 0);
+    }
+
+    #[test]
+    fn test_split_whitespace_matches_std() {
+        let mut rng = XorShift::new(0xfeed);
+        let alphabet = ['a', 'é', ' ', '\t', '\n', '\r', '\u{a0}', '\u{2003}', '😀'];
+
+        for _ in 0..1_000 {
+            let s = rng.string_from(&alphabet, 30);
+            let inline = InlineStr::from(&s);
+
+            let expected: Vec<&str> = s.split_whitespace().collect();
+            assert_eq!(inline.split_whitespace().collect::<Vec<_>>(), expected);
+
+            let expected: Vec<&str> = s.split_ascii_whitespace().collect();
+            assert_eq!(
+                inline.split_ascii_whitespace().collect::<Vec<_>>(),
+                expected
+            );
+
+            let expected: Vec<&str> = s.split_whitespace().rev().collect();
+            assert_eq!(
+                inline.split_whitespace().rev().collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_strip_prefix_suffix() {
+        let branch = InlineStr::from("refs/heads/main");
+        assert_eq!(branch.strip_prefix("refs/heads/").unwrap(), "main");
+        assert_eq!(branch.strip_prefix("refs/tags/"), None);
+        assert_eq!(branch.strip_prefix("").unwrap(), branch);
+        assert_eq!(branch.strip_prefix("refs/heads/main").unwrap(), "");
+        assert_eq!(branch.strip_prefix('r').unwrap(), "efs/heads/main");
+        assert_eq!(branch.strip_prefix(['x', 'r']).unwrap(), "efs/heads/main");
+        assert_eq!(
+            branch
+                .strip_prefix(|c: char| c.is_ascii_lowercase())
+                .unwrap(),
+            "efs/heads/main"
+        );
+
+        let file = InlineStr::from("config.json");
+        assert_eq!(file.strip_suffix(".json").unwrap(), "config");
+        assert_eq!(file.strip_suffix(".toml"), None);
+        assert_eq!(file.strip_suffix("").unwrap(), file);
+        assert_eq!(file.strip_suffix("config.json").unwrap(), "");
+        assert_eq!(file.strip_suffix('n').unwrap(), "config.jso");
+        assert_eq!(file.strip_suffix('x'), None);
+
+        let unicode = InlineStr::from("€uro→");
+        assert_eq!(unicode.strip_prefix("€").unwrap(), "uro→");
+        assert_eq!(unicode.strip_prefix('€').unwrap(), "uro→");
+        assert_eq!(unicode.strip_suffix('→').unwrap(), "€uro");
+        assert_eq!(unicode.strip_suffix(&String::from("ro→")).unwrap(), "€u");
+    }
+
+    #[test]
+    fn test_strip_borrowed() {
+        let text = InlineStr::from("foobar, stored on the heap");
+
+        let rest: &str = text.as_str().strip_prefix("foo").unwrap();
+        assert_eq!(rest, "bar, stored on the heap");
+        assert_eq!(rest.as_ptr(), text[3..].as_ptr());
+        assert_eq!(
+            text.as_str().strip_suffix(" heap"),
+            Some("foobar, stored on the")
+        );
+        assert_eq!(text.as_str().strip_prefix("baz"), None);
+    }
+
+    #[test]
+    fn test_strip_prefix_owned_is_inline() {
+        let bar = InlineStr::from("foobar").strip_prefix("foo").unwrap();
+        assert_eq!(bar, "bar");
+        assert!(bar.is_inline());
+
+        let long = InlineStr::from("a long prefix, then bar");
+        assert!(long.is_heap_allocated());
+        let bar = long.strip_prefix("a long prefix, then ").unwrap();
+        assert_eq!(bar, "bar");
+        assert!(bar.is_inline());
+    }
+
+    #[test]
+    fn test_utf8_len_of_chars() {
+        assert_eq!(utf8_len_of_chars(core::iter::empty()), 0);
+        assert_eq!(utf8_len_of_chars(['a', 'é', '€', '😀']), 1 + 2 + 3 + 4);
+
+        let mut rng = XorShift::new(0x1e17);
+        for _ in 0..100 {
+            let text = rng.string(20);
+            assert_eq!(utf8_len_of_chars(text.chars()), text.len());
+            assert_eq!(
+                InlineStr::will_inline(utf8_len_of_chars(text.chars())),
+                InlineStr::from(&text).is_inline()
+            );
+        }
+    }
+
+    #[test]
+    fn test_reversed() {
+        assert_eq!(InlineStr::from("abc").reversed(), "cba");
+        assert_eq!(InlineStr::from("").reversed(), "");
+        assert_eq!(InlineStr::from("日本語😀").reversed(), "😀語本日");
+        assert!(InlineStr::from("héllo").reversed().is_inline());
+
+        let long = "a much longer string that is stored on the heap";
+        let reversed = InlineStr::from(long).reversed();
+        assert_eq!(reversed, long.chars().rev().collect::<String>());
+        assert_eq!(reversed.reversed(), long);
+
+        // Combining marks are reversed along with everything else
+        assert_eq!(InlineStr::from("e\u{301}x").reversed(), "x\u{301}e");
+
+        assert_eq!(
+            count_allocations(|| {
+                InlineStr::from("abcdef").reversed();
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn test_split_at_inline() {
+        let value = InlineStr::from("héllo, a longer wörld");
+        for mid in (0..=value.len()).filter(|&mid| value.is_char_boundary(mid)) {
+            let (a, b) = value.split_at_inline(mid);
+            assert_eq!((a.as_str(), b.as_str()), value.split_at(mid));
+        }
+
+        let (a, b) = InlineStr::from("key:value").split_at_inline(3);
+        assert_eq!((a, b), ("key".into(), ":value".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_inline_mid_char() {
+        InlineStr::from("héllo").split_at_inline(2);
+    }
+
+    #[test]
+    fn test_split_once() {
+        let pair = |s: &str, d: &str| {
+            let (a, b) = InlineStr::from(s).split_once(d).unwrap();
+            (a.to_string(), b.to_string())
+        };
+        let rpair = |s: &str, d: &str| {
+            let (a, b) = InlineStr::from(s).rsplit_once(d).unwrap();
+            (a.to_string(), b.to_string())
+        };
+
+        assert_eq!(pair("key=value=x", "="), ("key".into(), "value=x".into()));
+        assert_eq!(rpair("key=value=x", "="), ("key=value".into(), "x".into()));
+        assert_eq!(pair("=value", "="), ("".into(), "value".into()));
+        assert_eq!(rpair("key=", "="), ("key".into(), "".into()));
+        assert_eq!(pair("a::b::c", "::"), ("a".into(), "b::c".into()));
+        assert_eq!(rpair("a::b::c", "::"), ("a::b".into(), "c".into()));
+        assert_eq!(pair("日本→語→x", "→"), ("日本".into(), "語→x".into()));
+        assert_eq!(rpair("日本→語→x", "→"), ("日本→語".into(), "x".into()));
+
+        let host = InlineStr::from("localhost:8080");
+        let (name, port) = host.split_once(':').unwrap();
+        drop(host);
+        assert_eq!((name, port), ("localhost".into(), "8080".into()));
+
+        assert_eq!(InlineStr::from("no delimiter").split_once('='), None);
+        assert_eq!(InlineStr::from("no delimiter").rsplit_once("::"), None);
+        assert_eq!(InlineStr::from("").split_once(':'), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let template = InlineStr::from("{name} is {age}{unit}");
+        let is_brace = |c: char| c == '{' || c == '}';
+        assert_eq!(template.matches(is_brace).count(), 6);
+        assert_eq!(
+            template.match_indices('{').collect::<Vec<_>>(),
+            [(0, "{".into()), (10, "{".into()), (15, "{".into())]
+        );
+
+        let abcabc = InlineStr::from("abcabc").matches("bc").collect::<Vec<_>>();
+        assert_eq!(abcabc, ["bc", "bc"]);
+        assert!(abcabc.iter().all(InlineStr::is_inline));
+
+        let aaaa = InlineStr::from("aaaaa");
+        assert_eq!(aaaa.matches("aa").collect::<Vec<_>>(), ["aa", "aa"]);
+        assert_eq!(
+            aaaa.match_indices("aa").collect::<Vec<_>>(),
+            [(0, "aa".into()), (2, "aa".into())]
+        );
+
+        let edges = InlineStr::from("x-y-x");
+        assert_eq!(
+            edges.match_indices('x').collect::<Vec<_>>(),
+            [(0, "x".into()), (4, "x".into())]
+        );
+
+        let empty: Vec<_> = InlineStr::from("ab").match_indices("").collect();
+        assert_eq!(empty, [(0, "".into()), (1, "".into()), (2, "".into())]);
+    }
+
+    #[test]
+    fn test_rsplit_to_inline() {
+        let dotted = InlineStr::from("a.b.c");
+        assert_eq!(
+            dotted.rsplit_to_inline('.').collect::<Vec<_>>(),
+            ["c", "b", "a"]
+        );
+        // `str::rsplit` is still reachable through `Deref`.
+        assert_eq!(dotted.rsplit('.').collect::<Vec<&str>>(), ["c", "b", "a"]);
+
+        let path = InlineStr::from("/usr/local/€/bin/");
+        assert!(path
+            .rsplit_to_inline('/')
+            .eq(path.as_str().rsplit('/').map(InlineStr::from)));
+        assert!(path
+            .rsplit_to_inline("/l")
+            .eq(path.as_str().rsplit("/l").map(InlineStr::from)));
+        assert_eq!(
+            InlineStr::from("")
+                .rsplit_to_inline(',')
+                .collect::<Vec<_>>(),
+            [""]
+        );
+    }
+
+    #[test]
+    fn test_split_collect() {
+        let (parts, count) = InlineStr::from("a.b.c").split_collect::<4, _>('.');
+        assert_eq!(count, 3);
+        assert_eq!(parts, ["a", "b", "c", ""]);
+        assert!(parts.iter().all(InlineStr::is_inline));
+
+        let (parts, count) = InlineStr::from("host:8080").split_collect::<2, _>(':');
+        assert_eq!(count, 2);
+        assert_eq!(parts, ["host", "8080"]);
+    }
+
+    #[test]
+    fn test_split_collect_overflow() {
+        let (parts, count) = InlineStr::from("a.b.c.d").split_collect::<2, _>('.');
+        assert_eq!(count, 2);
+        assert_eq!(parts, ["a", "b.c.d"]);
+
+        let (parts, count) = InlineStr::from("a.b").split_collect::<1, _>('.');
+        assert_eq!(count, 1);
+        assert_eq!(parts, ["a.b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "array length must be non-zero")]
+    fn test_split_collect_empty_array() {
+        let _ = InlineStr::from("a.b").split_collect::<0, _>('.');
+    }
+
+    #[test]
+    fn test_matches_std_equivalence() {
+        let haystack = "a€b€€c ab abab €";
+        let inline = InlineStr::from(haystack);
+
+        let check = |ours: Vec<(usize, InlineStr)>, theirs: Vec<(usize, &str)>| {
+            let theirs: Vec<(usize, String)> = theirs
+                .into_iter()
+                .map(|(i, m)| (i, m.to_string()))
+                .collect();
+            let ours: Vec<(usize, String)> =
+                ours.into_iter().map(|(i, m)| (i, m.to_string())).collect();
+            assert_eq!(ours, theirs);
+        };
+
+        check(
+            inline.match_indices('€').collect(),
+            haystack.match_indices('€').collect(),
+        );
+        check(
+            inline.match_indices("ab").collect(),
+            haystack.match_indices("ab").collect(),
+        );
+        check(
+            inline.match_indices("").collect(),
+            haystack.match_indices("").collect(),
+        );
+        check(
+            inline.match_indices(&['a', '€'][..]).collect(),
+            haystack.match_indices(&['a', '€'][..]).collect(),
+        );
+        check(
+            inline.match_indices(char::is_whitespace).collect(),
+            haystack.match_indices(char::is_whitespace).collect(),
+        );
+
+        let ours: Vec<String> = inline.matches("€€").map(|m| m.to_string()).collect();
+        let theirs: Vec<String> = haystack.matches("€€").map(String::from).collect();
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    fn test_escape() {
+        let inputs = [
+            "",
+            "plain",
+            "\"quoted\"",
+            "back\\slash",
+            "multi\nline\r\n",
+            "\u{0}\u{7}\u{1b}\u{7f}",
+            "héllo wörld",
+            "日本語 😀",
+            "\u{301}combining",
+        ];
+
+        for input in inputs {
+            let inline = InlineStr::from(input);
+            assert_eq!(inline.escape_debug(), input.escape_debug().to_string());
+            assert_eq!(inline.escape_default(), input.escape_default().to_string());
+            assert_eq!(inline.escape_unicode(), input.escape_unicode().to_string());
+        }
+
+        assert_eq!(InlineStr::from("é").escape_debug(), "é");
+        assert_eq!(InlineStr::from("é").escape_default(), "\\u{e9}");
+        assert_eq!(InlineStr::from("a\"b").escape_default(), "a\\\"b");
+    }
+
+    #[test]
+    fn test_retain_chars() {
+        let text = InlineStr::from("a1b2c3 déjà vu");
+
+        assert_eq!(text.retain_chars(|c| !c.is_ascii_digit()), "abc déjà vu");
+        assert_eq!(text.retain_chars(|_| false), "");
+        assert_eq!(text.retain_chars(|c| c != 'a'), "1b2c3 déjà vu");
+        assert_eq!(text.retain_chars(|c| c.is_ascii()), "a1b2c3 dj vu");
+        assert_eq!(text.retain_chars(|c| c != 'u'), "a1b2c3 déjà v");
+
+        let all = text.retain_chars(|_| true);
+        assert_eq!(all, text);
+        assert_eq!(all.as_ptr(), text.as_ptr());
+    }
+
+    #[test]
+    fn test_map_chars() {
+        let text = InlineStr::from("abc");
+        assert_eq!(text.map_chars(|c| c.to_ascii_uppercase()), "ABC");
+
+        let grown = text.map_chars(|_| '😀');
+        assert_eq!(grown, "😀😀😀");
+        assert_eq!(grown.len(), 12);
+        assert_eq!(grown.map_chars(|_| 'x'), "xxx");
+
+        assert_eq!(
+            InlineStr::from("日本é").map_chars(|c| if c == 'é' { 'e' } else { c }),
+            "日本e"
+        );
+        assert_eq!(InlineStr::from("").map_chars(|_| 'x'), "");
+
+        let long = InlineStr::from("a long string living on the heap");
+        let same = long.map_chars(|c| c);
+        assert_eq!(same, long);
+        assert_eq!(same.as_ptr(), long.as_ptr());
+    }
+
+    #[test]
+    fn test_into_bytes() {
+        assert_eq!(Vec::<u8>::from(InlineStr::from("abc")), b"abc".to_vec());
+        assert_eq!(
+            Vec::<u8>::from(InlineStr::from("héllo, wörld")),
+            "héllo, wörld".as_bytes()
+        );
+        assert!(Vec::<u8>::from(InlineStr::from("")).is_empty());
+    }
+
+    #[test]
+    fn test_padding_matches_format() {
+        for text in ["", "a", "ab", "abc", "日本", "a longer string"] {
+            let inline = InlineStr::from(text);
+
+            for width in 0..20 {
+                assert_eq!(inline.pad_start(width, ' '), format!("{text:>width$}"));
+                assert_eq!(inline.pad_end(width, ' '), format!("{text:<width$}"));
+                assert_eq!(inline.center(width, ' '), format!("{text:^width$}"));
+                assert_eq!(inline.center(width, '*'), format!("{text:*^width$}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_multibyte_fill() {
+        let text = InlineStr::from("ab");
+
+        let padded = text.pad_start(5, '→');
+        assert_eq!(padded, "→→→ab");
+        assert_eq!(padded.len(), 11);
+        assert_eq!(text.pad_end(4, 'é'), "abéé");
+        assert_eq!(text.center(5, '😀'), "😀ab😀😀");
+
+        let wide = InlineStr::from("a string wider than the width");
+        let same = wide.pad_start(4, '→');
+        assert_eq!(same.as_ptr(), wide.as_ptr());
+        assert_eq!(wide.center(0, ' '), wide);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_into_os_string_and_path_buf() {
+        let name = InlineStr::from("config/settings.toml");
+
+        let os_string = OsString::from(name.clone());
+        assert_eq!(os_string, "config/settings.toml");
+
+        let path = PathBuf::from(name);
+        assert_eq!(path, std::path::Path::new("config/settings.toml"));
+        assert_eq!(path.file_name().unwrap(), "settings.toml");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_os_str_and_path() {
+        let path = Path::new("config/settings.toml");
+        assert_eq!(InlineStr::try_from(path).unwrap(), "config/settings.toml");
+        assert_eq!(
+            InlineStr::try_from(OsStr::new("settings.toml")).unwrap(),
+            "settings.toml"
+        );
+        assert_eq!(InlineStr::from_path_lossy(path), "config/settings.toml");
+        assert_eq!(InlineStr::from_os_str_lossy(OsStr::new("€")), "€");
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_os_str_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = OsStr::from_bytes(b"Cargo\xff.toml");
+        assert_eq!(InlineStr::try_from(invalid).unwrap_err().valid_up_to(), 5);
+        assert!(InlineStr::try_from(Path::new(invalid)).is_err());
+        assert_eq!(InlineStr::from_os_str_lossy(invalid), "Cargo\u{FFFD}.toml");
+        assert_eq!(
+            InlineStr::from_path_lossy(Path::new(invalid)),
+            "Cargo\u{FFFD}.toml"
+        );
+    }
+
+    #[test]
+    fn test_common_prefix_suffix() {
+        let key = InlineStr::from("user/1234/profile");
+
+        assert_eq!(key.common_prefix("user/1234/profile"), key);
+        assert_eq!(key.common_suffix("user/1234/profile"), key);
+        assert_eq!(key.common_prefix("group/1/profile"), "");
+        assert_eq!(key.common_suffix("group/1/settings"), "");
+        assert_eq!(key.common_prefix("user/1299"), "user/12");
+        assert_eq!(key.common_suffix("group/1/profile"), "/profile");
+        assert_eq!(key.common_prefix("user/"), "user/");
+        assert_eq!(key.common_prefix_len("user/1234/profile/avatar"), key.len());
+        assert_eq!(key.common_prefix_len(""), 0);
+
+        // '€' is E2 82 AC and '₭' is E2 82 AD: the shared lead bytes must not be split
+        let euro = InlineStr::from("a€");
+        assert_eq!(euro.common_prefix_len("a₭"), 1);
+        assert_eq!(euro.common_prefix("a₭"), "a");
+
+        // 'Ā' is C4 80 and 'Ȁ' is C8 80: the shared trailing byte must not be split
+        let macron = InlineStr::from("Āz");
+        assert_eq!(macron.common_suffix_len("Ȁz"), 1);
+        assert_eq!(macron.common_suffix("Ȁz"), "z");
+    }
+
+    #[test]
+    fn test_cstring() {
+        let clean = InlineStr::from("hello");
+        assert_eq!(clean.to_cstring().unwrap().as_bytes_with_nul(), b"hello\0");
+
+        let err = InlineStr::from("hel\0lo").to_cstring().unwrap_err();
+        assert_eq!(err.nul_position(), 3);
+
+        let c_str = c"héllo wörld";
+        let from_c = InlineStr::try_from(c_str).unwrap();
+        assert_eq!(from_c, "héllo wörld");
+        assert_eq!(from_c.to_cstring().unwrap().as_c_str(), c_str);
+
+        let invalid = c"\xff\xfe";
+        assert!(InlineStr::try_from(invalid).is_err());
+    }
+
+    #[test]
+    fn test_from_utf8_matches_std() {
+        let mut rng = XorShift::new(0x0f8);
+        let mut corpus: Vec<Vec<u8>> = vec![
+            b"".to_vec(),
+            b"\xff".to_vec(),
+            b"abc\xc3".to_vec(),
+            b"\xed\xa0\x80".to_vec(),
+            b"\xf4\x90\x80\x80".to_vec(),
+            b"\xc0\xaf".to_vec(),
+        ];
+        for _ in 0..500 {
+            let mut bytes = rng.string(300).into_bytes();
+            // Corrupt about half of the inputs at a random position.
+            if !bytes.is_empty() && rng.next() & 1 == 0 {
+                let idx = rng.next() as usize % bytes.len();
+                bytes[idx] = rng.next() as u8 | 0x80;
+            }
+            corpus.push(bytes);
+        }
+
+        for bytes in &corpus {
+            let expected = str::from_utf8(bytes);
+            assert_eq!(from_utf8(bytes), expected);
+            assert_eq!(
+                InlineStr::try_from(bytes.as_slice()).map_err(|err| err.valid_up_to()),
+                expected
+                    .map(InlineStr::from)
+                    .map_err(|err| err.valid_up_to())
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_chunks() {
+        let chunks = |s: &str, n: usize| InlineStr::from(s).char_chunks(n).collect::<Vec<_>>();
+
+        assert_eq!(chunks("ABCDEFGHIJKL", 4), ["ABCD", "EFGH", "IJKL"]);
+        assert_eq!(chunks("ABCDEFGHIJ", 4), ["ABCD", "EFGH", "IJ"]);
+        assert_eq!(chunks("AB", 4), ["AB"]);
+        assert_eq!(chunks("日本語テキスト", 3), ["日本語", "テキス", "ト"]);
+        assert_eq!(chunks("a😀b😀", 1), ["a", "😀", "b", "😀"]);
+        assert!(chunks("", 3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_char_chunks_zero() {
+        let _ = InlineStr::from("abc").char_chunks(0);
+    }
+
+    #[test]
+    fn test_slice_chars() {
+        let text = InlineStr::from("hi 😀 wörld 🎉!");
+
+        assert_eq!(text.slice_chars(..), text);
+        assert_eq!(text.slice_chars(..2), "hi");
+        assert_eq!(text.slice_chars(3..4), "😀");
+        assert_eq!(text.slice_chars(5..=9), "wörld");
+        assert_eq!(text.slice_chars(11..), "🎉!");
+        assert_eq!(text.slice_chars(13..), "");
+        assert_eq!(text.slice_chars(4..4), "");
+
+        assert_eq!(text.get_chars(..14), None);
+        assert_eq!(text.get_chars(14..), None);
+        let (start, end) = (3, 2);
+        assert_eq!(text.get_chars(start..end), None);
+        assert_eq!(text.get_chars(..=usize::MAX), None);
+        assert_eq!(text.get_chars(..13).unwrap(), text);
+
+        let count = text.chars().count();
+        for a in 0..=count {
+            for b in a..=count {
+                let expected: String = text.chars().skip(a).take(b - a).collect();
+                assert_eq!(text.slice_chars(a..b), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds of a string with 3 chars")]
+    fn test_slice_chars_out_of_bounds() {
+        InlineStr::from("a😀b").slice_chars(1..4);
+    }
+
+    #[test]
+    fn test_edits() {
+        let text = InlineStr::from("héllo");
+
+        assert_eq!(text.inserted(0, ">> "), ">> héllo");
+        assert_eq!(text.inserted(text.len(), "!"), "héllo!");
+        assert_eq!(text.inserted(3, "-"), "hé-llo");
+        assert_eq!(text.removed_range(..3), "llo");
+        assert_eq!(text.removed_range(3..), "hé");
+        assert_eq!(text.removed_range(..), "");
+        assert_eq!(text.removed_range(3..3), text);
+        assert_eq!(text.spliced(1..3, "e"), "hello");
+        assert_eq!(
+            text.spliced(1..3, "ey, what a lovely day to say he"),
+            "hey, what a lovely day to say hello"
+        );
+        assert_eq!(text.spliced(..0, ""), text);
+
+        let long = InlineStr::from("a string long enough for the heap");
+        assert_eq!(long.removed_range(3..), "a s");
+        assert_eq!(long.spliced(1.., "h"), "ah");
+        assert_eq!(InlineStr::from("ab").inserted(1, "-------"), "a-------b");
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a char boundary")]
+    fn test_inserted_char_boundary() {
+        InlineStr::from("héllo").inserted(2, "x");
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a char boundary")]
+    fn test_spliced_char_boundary() {
+        InlineStr::from("héllo").spliced(0..2, "x");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_removed_range_out_of_bounds() {
+        InlineStr::from("hello").removed_range(3..10);
+    }
+
+    #[test]
+    fn test_normalize_newlines() {
+        let lf = |s: &str| InlineStr::from(s).normalize_newlines();
+        let crlf = |s: &str| InlineStr::from(s).normalize_newlines_to(LineEnding::CrLf);
+
+        assert_eq!(lf("a\r\nb\rc\nd"), "a\nb\nc\nd");
+        assert_eq!(lf("trailing\r"), "trailing\n");
+        assert_eq!(lf("before\r\n"), "before\n");
+        assert_eq!(lf("\r\r\n\n"), "\n\n\n");
+        assert_eq!(lf(""), "");
+
+        assert_eq!(crlf("a\r\nb\rc\nd"), "a\r\nb\r\nc\r\nd");
+        assert_eq!(crlf("trailing\r"), "trailing\r\n");
+        assert_eq!(crlf("\n\r"), "\r\n\r\n");
+
+        let normalized = InlineStr::from("already\nnormalized\ntext\n");
+        assert_eq!(
+            normalized.normalize_newlines().as_ptr(),
+            normalized.as_ptr()
+        );
+
+        let windows = InlineStr::from("already\r\nwindows\r\n");
+        let same = windows.normalize_newlines_to(LineEnding::CrLf);
+        assert_eq!(same.as_ptr(), windows.as_ptr());
+    }
+
+    #[test]
+    fn test_cmp_codepoints() {
+        let cmp = |a: &str, b: &str| InlineStr::from(a).cmp_codepoints(&InlineStr::from(b));
+
+        assert_eq!(cmp("Z", "a"), Ordering::Less);
+        assert_eq!(cmp("abc", "abc"), Ordering::Equal);
+        assert_eq!(cmp("ab", "abc"), Ordering::Less);
+        assert_eq!(cmp("", "a"), Ordering::Less);
+
+        // Boundaries between UTF-8 encoding lengths, and a surrogate-adjacent pair
+        let ordered = [
+            "\u{7f}",
+            "\u{80}",
+            "\u{7ff}",
+            "\u{800}",
+            "\u{d7ff}",
+            "\u{e000}",
+            "\u{ffff}",
+            "\u{10000}",
+            "\u{10ffff}",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(cmp(pair[0], pair[1]), Ordering::Less);
+            assert!(pair[0].chars().lt(pair[1].chars()));
+        }
+
+        let mut rng = XorShift::new(0xc0de);
+        for _ in 0..1_000 {
+            let (a, b) = (rng.string(8), rng.string(8));
+            assert_eq!(cmp(&a, &b), a.chars().cmp(b.chars()));
+        }
+    }
+
+    #[test]
+    fn test_dedent() {
+        let dedent = |s: &str| InlineStr::from(s).dedent();
+
+        assert_eq!(
+            dedent("    SELECT *\n      FROM t\n\n    WHERE x\n"),
+            "SELECT *\n  FROM t\n\nWHERE x\n"
+        );
+        assert_eq!(dedent("  a\n      \n  b"), "a\n    \nb");
+        assert_eq!(dedent("    a\n  \n    b\n"), "a\n\nb\n");
+        assert_eq!(dedent("\tkey: 1\n\t\tnested: 2\n"), "key: 1\n\tnested: 2\n");
+        assert_eq!(dedent("\t a\n  b\n"), "\t a\n  b\n");
+        assert_eq!(dedent("  a\r\n    b\r\n  \r\n"), "a\r\n  b\r\n\r\n");
+        assert_eq!(dedent("   \n  \n"), "   \n  \n");
+        assert_eq!(dedent(""), "");
+
+        let flush = InlineStr::from("first line at column 0\n    indented\n");
+        assert_eq!(flush.dedent().as_ptr(), flush.as_ptr());
+    }
+
+    #[test]
+    fn test_index() {
+        let text = InlineStr::from("héllo wörld");
+
+        assert_eq!(&text[0..1], "h");
+        assert_eq!(&text[7..], "wörld");
+        assert_eq!(&text[..6], "héllo");
+        assert_eq!(&text[..], "héllo wörld");
+        assert_eq!(&text[1..=2], "é");
+        assert_eq!(&text[..=2], "hé");
+        assert_eq!(&text[(Bound::Excluded(0), Bound::Included(2))], "é");
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 2 is not a char boundary")]
+    fn test_index_char_boundary() {
+        let _ = &InlineStr::from("héllo")[..2];
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 10 is out of bounds")]
+    fn test_index_out_of_bounds() {
+        let _ = &InlineStr::from("héllo")[10..];
+    }
+
+    #[test]
+    fn test_short_strings_always_inline() {
+        let heap_source = String::from("a string long enough to live on the heap");
+        let long = InlineStr::from(heap_source.clone());
+        assert!(long.is_heap_allocated());
+
+        let values = [
+            InlineStr::from(heap_source[..7].to_string()),
+            InlineStr::from(&heap_source[2..5]),
+            long.strip_prefix("a string long enough to live on the ")
+                .unwrap(),
+            long.slice_chars(..7),
+        ];
+        for value in &values {
+            assert!(value.is_inline(), "{value:?} is not inline");
+        }
+    }
+
+    #[test]
+    fn test_inline_capacity() {
+        let source = "x".repeat(2 * InlineStr::INLINE_CAPACITY);
+        for len in 0..source.len() {
+            let value = InlineStr::from(&source[..len]);
+            assert_eq!(InlineStr::will_inline(len), value.is_inline(), "{len}");
+            assert_eq!(
+                InlineStr::will_inline(len),
+                len <= InlineStr::INLINE_CAPACITY
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_until_nul() {
+        let until_nul = |bytes: &[u8]| InlineStr::from_bytes_until_nul(bytes);
+
+        assert_eq!(until_nul(b"\0hello").unwrap(), "");
+        assert_eq!(until_nul(b"").unwrap(), "");
+        assert_eq!(until_nul(b"hel\0lo").unwrap(), "hel");
+        assert_eq!(until_nul(b"hello").unwrap(), "hello");
+        assert_eq!(until_nul("héllo wörld".as_bytes()).unwrap(), "héllo wörld");
+        assert_eq!(until_nul(b"ok\0\xff\xfe garbage").unwrap(), "ok");
+
+        let mut padded = [0; 100];
+        padded[..27].copy_from_slice(b"a much longer padded string");
+        let value = until_nul(&padded).unwrap();
+        assert_eq!(value, "a much longer padded string");
+        assert!(value.is_heap_allocated());
+
+        let err = until_nul(b"ab\xff\0").unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+    }
+
+    #[test]
+    fn test_from_ascii() {
+        assert_eq!(InlineStr::from_ascii(b"").unwrap(), "");
+        assert_eq!(
+            InlineStr::from_ascii(b"x-request-id").unwrap(),
+            "x-request-id"
+        );
+        assert_eq!(InlineStr::from_ascii(b"\xffabc").unwrap_err().position(), 0);
+        assert_eq!(InlineStr::from_ascii(b"abc\x80").unwrap_err().position(), 3);
+        assert_eq!(
+            InlineStr::from_ascii("abé".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "non-ASCII byte at position 2"
+        );
+
+        let mut rng = XorShift::new(0xa5c1);
+        for _ in 0..1000 {
+            let len = rng.next() as usize % 32;
+            // Mostly ASCII, so that plenty of inputs are accepted
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| (rng.next() as u8) & if rng.next() & 7 == 0 { 0xff } else { 0x7f })
+                .collect();
+
+            match InlineStr::from_ascii(&bytes) {
+                Ok(value) => {
+                    assert_eq!(value.as_bytes(), bytes);
+                    assert_eq!(str::from_utf8(&bytes), Ok(value.as_str()));
+                }
+                Err(err) => {
+                    assert!(!bytes[err.position()].is_ascii());
+                    assert!(bytes[..err.position()].is_ascii());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        for text in ["", "short", "héllo wörld, a longer string on the heap"] {
+            let value = InlineStr::from(text);
+            assert_eq!(value.validate(), Ok(()));
+
+            let unchecked = unsafe { InlineStr::from_utf8_unchecked(text.as_bytes()) };
+            assert_eq!(unchecked.validate(), Ok(()));
+            assert_eq!(unchecked, value);
+        }
+
+        let corrupted = unsafe { InlineStr::from_utf8_unchecked(b"ab\xff") };
+        assert_eq!(corrupted.validate().unwrap_err().valid_up_to(), 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    #[should_panic(expected = "InlineStr holds invalid UTF-8")]
+    fn test_debug_validate_catches_corruption() {
+        let corrupted = unsafe { InlineStr::from_utf8_unchecked(b"ab\xff") };
+        corrupted.as_str();
+    }
+
+    #[test]
+    fn test_try_inline() {
+        let source = "é".repeat(InlineStr::INLINE_CAPACITY);
+        let fits = &source[..InlineStr::INLINE_CAPACITY - 1];
+        let value = InlineStr::try_inline(fits).unwrap();
+        assert_eq!(value, fits);
+        assert!(value.is_inline());
+
+        let err = InlineStr::try_inline(&source).unwrap_err();
+        assert_eq!(err.attempted_len(), source.len());
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "string of {} bytes exceeds the inline capacity of {} bytes",
+                source.len(),
+                InlineStr::INLINE_CAPACITY
+            )
+        );
+    }
+
+    #[test]
+    fn test_display_formatting() {
+        for text in [
+            "abc",
+            "héllo",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = InlineStr::from(text);
+            assert_eq!(format!("{value:>10}"), format!("{text:>10}"));
+            assert_eq!(format!("{value:<10}|"), format!("{text:<10}|"));
+            assert_eq!(format!("{value:*^9}"), format!("{text:*^9}"));
+            assert_eq!(format!("{value:.2}"), format!("{text:.2}"));
+            assert_eq!(format!("{value:-<8.4}"), format!("{text:-<8.4}"));
+        }
+
+        assert_eq!(format!("{:>5}", InlineStr::from("ab")), "   ab");
+        assert_eq!(format!("{:.3}", InlineStr::from("héllo")), "hél");
+        assert_eq!(format!("{:_^6}", InlineStr::from("ab")), "__ab__");
+    }
+
+    #[test]
+    fn test_stable_hash_vectors() {
+        let vectors = [
+            ("", 0xcbf29ce484222325, 0xcbf29ce48422230f),
+            ("a", 0xaf63dc4c8601ec8c, 0xaf64064c860233ea),
+            ("foobar", 0x85944171f73967e8, 0x86d04911560043b6),
+            ("héllo", 0xa35ff71f960240e0, 0x61168d902e6ae4aa),
+            (
+                "a much longer string that is stored on the heap",
+                0x50c590daaec98e7b,
+                0xe630e054b7392575,
+            ),
+        ];
+        for (text, hash, seeded) in vectors {
+            let value = InlineStr::from(text);
+            assert_eq!(value.stable_hash(), hash, "{text:?}");
+            assert_eq!(value.stable_hash_with_seed(0), hash, "{text:?}");
+            assert_eq!(value.stable_hash_with_seed(42), seeded, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_stable_hash_representation() {
+        let long = InlineStr::from("a much longer string, then short");
+        let heap = long.clone();
+        let inline = long.strip_prefix("a much longer string, then ").unwrap();
+        assert!(heap.is_heap_allocated() && inline.is_inline());
+
+        assert_eq!(inline.stable_hash(), InlineStr::from("short").stable_hash());
+        assert_eq!(heap.stable_hash(), long.stable_hash());
+        assert_eq!(
+            InlineStr::from(String::from("a much longer string, then short")).stable_hash(),
+            long.stable_hash()
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(format!("{:x}", InlineStr::from("AB")), "4142");
+        assert_eq!(format!("{:X}", InlineStr::from("\n\u{1b}z")), "0A1B7A");
+        assert_eq!(format!("{:x}", InlineStr::from("é")), "c3a9");
+        assert_eq!(format!("{:x}", InlineStr::EMPTY), "");
+
+        let long = InlineStr::from("a much longer string that is stored on the heap");
+        let expected: String = long.bytes().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(format!("{long:x}"), expected);
+    }
+
+    #[test]
+    fn test_ptr_eq() {
+        let text = "a much longer string that is stored on the heap";
+        let heap = InlineStr::from(text);
+        assert!(heap.ptr_eq(&heap.clone()));
+        assert!(!heap.ptr_eq(&InlineStr::from(text)));
+
+        let inline = InlineStr::from("short");
+        assert!(inline.ptr_eq(&inline.clone()));
+        assert!(inline.ptr_eq(&InlineStr::from("short")));
+        assert!(!inline.ptr_eq(&InlineStr::from("shorT")));
+
+        let tail = heap
+            .strip_prefix("a much longer string that is stored on the ")
+            .unwrap();
+        assert!(!tail.ptr_eq(&heap));
+        assert!(!heap.ptr_eq(&tail));
+        assert!(InlineStr::EMPTY.ptr_eq(&InlineStr::from("")));
+    }
+
+    #[test]
+    fn test_eq_char() {
+        assert_eq!(InlineStr::from("a"), 'a');
+        assert_eq!('€', InlineStr::from("€"));
+        assert_ne!(InlineStr::from("a"), 'b');
+        assert_ne!(InlineStr::from("ab"), 'a');
+        assert_ne!('a', InlineStr::from("aa"));
+        assert_ne!(InlineStr::EMPTY, 'a');
+        assert_ne!(InlineStr::from("é"), 'e');
+    }
+
+    #[test]
+    fn test_make_mut_unique() {
+        let mut value = InlineStr::from(String::from("A Much Longer String On The Heap"));
+
+        value.make_ascii_lowercase();
+        assert_eq!(value, "a much longer string on the heap");
+
+        value.make_mut()[..1].make_ascii_uppercase();
+        assert_eq!(value, "A much longer string on the heap");
+        assert!(value.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_make_mut_shared() {
+        let original = InlineStr::from("A Much Longer String On The Heap");
+        let mut value = original.clone();
+        let other = original.clone();
+
+        value.make_ascii_uppercase();
+        assert_eq!(value, "A MUCH LONGER STRING ON THE HEAP");
+        assert_eq!(original, "A Much Longer String On The Heap");
+        assert_eq!(other, "A Much Longer String On The Heap");
+        assert!(!value.ptr_eq(&original));
+        assert!(original.ptr_eq(&other));
+    }
+
+    #[test]
+    fn test_make_mut_inline() {
+        let original = InlineStr::from("KéY");
+        let mut value = original.clone();
+
+        let ptr = value.as_ptr();
+        value.make_ascii_lowercase();
+        assert_eq!(value, "kéy");
+        assert_eq!(value.as_ptr(), ptr);
+        assert_eq!(original, "KéY");
+
+        value.make_ascii_uppercase();
+        assert_eq!(value, "KéY");
+    }
+
+    #[test]
+    fn test_ascii_cased() {
+        assert_eq!(InlineStr::from("KéY").ascii_lowercased(), "kéy");
+        assert_eq!(InlineStr::from("kéy").ascii_uppercased(), "KéY");
+
+        let long = InlineStr::from("A Much Longer String On The Heap");
+        let shared = long.clone();
+        assert_eq!(
+            shared.ascii_lowercased(),
+            "a much longer string on the heap"
+        );
+        assert_eq!(long, "A Much Longer String On The Heap");
+    }
+
+    #[test]
+    fn test_ascii_cased_allocations() {
+        let lower = InlineStr::from("a much longer string on the heap");
+        let ptr = lower.as_ptr();
+        let mut result = InlineStr::EMPTY;
+        assert_eq!(count_allocations(|| result = lower.ascii_lowercased()), 0);
+        assert_eq!(result.as_ptr(), ptr);
+
+        let inline = InlineStr::from("KEY");
+        assert_eq!(count_allocations(|| result = inline.ascii_lowercased()), 0);
+        assert_eq!(result, "key");
+    }
+
+    #[test]
+    fn test_appended_prepended() {
+        let key = InlineStr::from("id")
+            .with_prepended("u:")
+            .with_appended(":7");
+        assert_eq!(key, "u:id:7");
+        assert!(key.is_inline());
+
+        let long = key.with_appended("/a longer suffix on the heap");
+        assert_eq!(long, "u:id:7/a longer suffix on the heap");
+        assert!(long.is_heap_allocated());
+
+        let ptr = long.as_ptr();
+        let same = long.with_appended("").with_prepended("");
+        assert_eq!(same.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_empty_const() {
+        const NAME: InlineStr = InlineStr::EMPTY;
+        assert!(NAME.is_empty());
+        assert!(NAME.is_inline());
+        assert_eq!(NAME, InlineStr::from(""));
+
+        let names: HashMap<u32, InlineStr> = HashMap::from([(1, InlineStr::from("one"))]);
+        assert_eq!(names.get(&1).cloned().unwrap_or(InlineStr::EMPTY), "one");
+        assert_eq!(names.get(&2).cloned().unwrap_or(InlineStr::EMPTY), "");
+    }
+
+    #[test]
+    fn test_heap_size() {
+        for len in [0, 1, InlineStr::INLINE_CAPACITY] {
+            let value = InlineStr::from("x".repeat(len));
+            assert_eq!(value.heap_size(), 0);
+            assert_eq!(value.total_size(), size_of::<InlineStr>());
+        }
+
+        for len in [InlineStr::INLINE_CAPACITY + 1, 255, 256, 10_000] {
+            let value = InlineStr::from("x".repeat(len));
+            assert!(value.heap_size() >= len, "{len}");
+            assert!(value.heap_size() < len + 16, "{len}");
+            assert_eq!(
+                value.total_size(),
+                size_of::<InlineStr>() + value.heap_size()
+            );
+
+            let clone = value.clone();
+            assert_eq!(clone.heap_size(), value.heap_size());
+        }
+    }
+
+    #[test]
+    fn test_storage_introspection() {
+        for (text, inline) in [
+            ("", true),
+            ("1234567", true),
+            ("12345678", false),
+            ("é", true),
+        ] {
+            let value = InlineStr::from(text);
+            assert_eq!(value.is_inline(), inline, "{text:?}");
+            assert_eq!(value.is_heap_allocated(), !inline, "{text:?}");
+
+            let clone = value.clone();
+            assert_eq!(clone.is_inline(), inline, "{text:?}");
+        }
+
+        // A 3 byte char right at the boundary
+        assert!(InlineStr::from("abcd€").is_inline());
+        assert!(InlineStr::from("abcde€").is_heap_allocated());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_os_str_and_path_cmp() {
+        let name = InlineStr::from("Cargo.toml");
+
+        assert_eq!(name, *OsStr::new("Cargo.toml"));
+        assert_eq!(*OsStr::new("Cargo.toml"), name);
+        assert_eq!(name, OsStr::new("Cargo.toml"));
+        assert_eq!(OsStr::new("Cargo.toml"), name);
+        assert_eq!(name, OsString::from("Cargo.toml"));
+        assert_eq!(OsString::from("Cargo.toml"), name);
+        assert_eq!(name, *Path::new("Cargo.toml"));
+        assert_eq!(Path::new("Cargo.toml"), name);
+        assert_eq!(name, PathBuf::from("Cargo.toml"));
+        assert_eq!(PathBuf::from("Cargo.toml"), name);
+
+        assert_ne!(name, OsStr::new("cargo.toml"));
+        assert_ne!(Path::new("CARGO.TOML"), name);
+        let (os_string, path_buf) = (OsString::from("Cargo.tomm"), PathBuf::from("Cargo"));
+        assert!(name < os_string);
+        assert!(os_string > name);
+        assert!(name > path_buf);
+        assert!(Path::new("Cargo") < name);
+
+        // Paths compare by component, just like `Path == Path`
+        let nested = InlineStr::from("src/lib.rs");
+        assert_eq!(nested, Path::new("src//lib.rs"));
+        assert_ne!(nested, OsStr::new("src//lib.rs"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_as_os_str_and_path() {
+        let name = InlineStr::from("src/lib.rs");
+
+        assert_eq!(name.as_os_str(), OsStr::new("src/lib.rs"));
+        assert_eq!(name.as_os_str().len(), name.len());
+        assert_eq!(name.as_path().extension(), Some(OsStr::new("rs")));
+        assert_eq!(name.as_path().parent(), Some(Path::new("src")));
+        assert_eq!(name.as_os_str().as_encoded_bytes().as_ptr(), name.as_ptr());
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_os_str_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = OsStr::from_bytes(b"Cargo\xff");
+        let name = InlineStr::from("Cargo");
+
+        assert_ne!(name, invalid);
+        assert_ne!(invalid, name);
+        assert_ne!(name, Path::new(invalid));
+        assert!(name < invalid);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let ascii = InlineStr::from("a key that is too long");
+        assert_eq!(ascii.truncate(5), "a key");
+        assert_eq!(ascii.truncate(0), "");
+        assert_eq!(ascii.truncate(100).as_ptr(), ascii.as_ptr());
+        assert_eq!(ascii.truncate(ascii.len()), ascii);
+
+        // 'é' is 2 bytes and '😀' is 4
+        let multibyte = InlineStr::from("hé😀!");
+        assert_eq!(multibyte.truncate(1), "h");
+        assert_eq!(multibyte.truncate(2), "h");
+        assert_eq!(multibyte.truncate(3), "hé");
+        assert_eq!(multibyte.truncate(6), "hé");
+        assert_eq!(multibyte.truncate(7), "hé😀");
+        assert_eq!(multibyte.truncate(8), "hé😀!");
+    }
+
+    #[test]
+    fn test_truncate_display() {
+        let text = InlineStr::from("a long cell value");
+        assert_eq!(text.truncate_display(8, "…"), "a long …");
+        assert_eq!(text.truncate_display(8, "..."), "a lon...");
+        assert_eq!(text.truncate_display(8, ""), "a long c");
+        assert_eq!(text.truncate_display(2, "..."), "a ");
+        assert_eq!(text.truncate_display(3, "..."), "...");
+
+        let unicode = InlineStr::from("日本語のテキスト");
+        assert_eq!(unicode.truncate_display(4, "…"), "日本語…");
+    }
+
+    #[test]
+    fn test_truncate_display_fits() {
+        let text = InlineStr::from("a cell value on the heap");
+        let chars = text.chars().count();
+        assert_eq!(text.truncate_display(chars, "…").as_ptr(), text.as_ptr());
+        assert_eq!(text.truncate_display(100, "…").as_ptr(), text.as_ptr());
+        assert_eq!(InlineStr::from("héllo").truncate_display(5, "…"), "héllo");
+        assert_eq!(InlineStr::EMPTY.truncate_display(0, "…"), "");
+    }
+
+    #[test]
+    fn test_is_ascii() {
+        assert!(InlineStr::from("abc").is_ascii());
+        assert!(InlineStr::EMPTY.is_ascii());
+        assert!(InlineStr::from("a long ASCII string\r\n\u{7f}").is_ascii());
+        assert!(!InlineStr::from("héllo").is_ascii());
+        assert!(!InlineStr::from("a long string that ends in a multibyte char: €").is_ascii());
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(size_of::<InlineStr>(), 8);
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(size_of::<Option<InlineStr>>(), 16);
+    }
+
+    #[test]
+    fn test_str_ord() {
+        let inline = InlineStr::from("mmm");
+        let (string, cow) = (String::from("zzz"), Cow::Borrowed("aaa"));
+
+        assert!(inline < "zzz");
+        assert!(inline > "aaa");
+        assert!("aaa" < inline);
+        assert!(inline < string);
+        assert!(string > inline);
+        assert!(inline > cow);
+        assert!(cow < inline);
+        assert!(inline <= "mmm");
+        assert!(inline >= "mmm");
+
+        assert_eq!(inline.partial_cmp(&"mmm"), Some(Ordering::Equal));
+        assert_eq!("mmm".partial_cmp(&inline), Some(Ordering::Equal));
+        assert!(*"aaa" < inline);
+        assert!(inline > *"aaa");
+        assert_eq!(
+            inline.partial_cmp(&String::from("mmm")),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            inline.partial_cmp(&Cow::Borrowed("mmmm")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Cow::Borrowed("mm").partial_cmp(&inline),
+            Some(Ordering::Less)
+        );
+
+        // The existing equality comparisons still infer without annotations
+        assert!(inline == "mmm");
+        assert!(inline != *"mm");
+        assert_eq!(inline, String::from("mmm"));
+
+        let sorted: Vec<InlineStr> = ["apple", "banana", "cherry", "a much longer durian"]
+            .into_iter()
+            .map(InlineStr::from)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let probe = |key: &str| sorted.binary_search_by(|s| s.partial_cmp(&key).unwrap());
+        assert_eq!(probe("banana"), Ok(2));
+        assert_eq!(probe("blueberry"), Err(3));
+        assert_eq!(probe("a much longer durian"), Ok(0));
+    }
+
+    #[test]
+    fn test_char_boundaries() {
+        // 'é' takes up bytes 1 and 2
+        let text = InlineStr::from("héllo");
+
+        let boundaries: Vec<bool> = (0..=7).map(|idx| text.is_char_boundary(idx)).collect();
+        assert_eq!(
+            boundaries,
+            [true, true, false, true, true, true, true, false]
+        );
+
+        let floors: Vec<usize> = (0..=7).map(|idx| text.floor_char_boundary(idx)).collect();
+        assert_eq!(floors, [0, 1, 1, 3, 4, 5, 6, 6]);
+
+        let emoji = InlineStr::from("😀😀");
+        let floors: Vec<usize> = (0..=8).map(|idx| emoji.floor_char_boundary(idx)).collect();
+        assert_eq!(floors, [0, 0, 0, 0, 4, 4, 4, 4, 8]);
+        assert_eq!(InlineStr::from("").floor_char_boundary(3), 0);
+    }
+
+    #[test]
+    fn test_as_ref_and_borrow_inline_str() {
+        fn total_len<I: AsRef<InlineStr>>(values: &[I]) -> usize {
+            values.iter().map(|value| value.as_ref().len()).sum()
+        }
+
+        fn contains<K: Borrow<InlineStr> + Ord>(set: &BTreeSet<K>, key: &InlineStr) -> bool {
+            set.iter().any(|value| value.borrow() == key)
+        }
+
+        let owned = [InlineStr::from("ab"), InlineStr::from("a longer string")];
+        assert_eq!(total_len(&owned), 17);
+        assert_eq!(total_len(&[&owned[0], &owned[1]]), 17);
+
+        let set = BTreeSet::from(owned.clone());
+        assert!(contains(&set, &owned[0]));
+        assert!(!contains(&set, &InlineStr::from("missing")));
+        assert!(set.contains(&InlineStr::from("ab")));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("héllo".parse::<InlineStr>(), Ok(InlineStr::from("héllo")));
+        assert_eq!("".parse::<InlineStr>(), Ok(InlineStr::EMPTY));
+    }
+
+    #[test]
+    fn test_bytes_eq() {
+        let key = InlineStr::from("héllo");
+        let bytes: &[u8] = "héllo".as_bytes();
+
+        assert_eq!(key, *bytes);
+        assert_eq!(*bytes, key);
+        assert_eq!(key, bytes);
+        assert_eq!(bytes, key);
+        assert!(key == b"h\xc3\xa9llo");
+        assert!(b"h\xc3\xa9llo" == key);
+        assert!(key == *b"h\xc3\xa9llo");
+        assert!(*b"h\xc3\xa9llo" == key);
+
+        let invalid: &[u8] = b"h\xffllo";
+        assert_ne!(key, invalid);
+        assert_ne!(invalid, key);
+        assert!(key != b"hello");
+        assert!(InlineStr::from("") == *b"");
+
+        let owned = bytes.to_vec();
+        assert_eq!(key, owned);
+        assert_eq!(owned, key);
+        assert_ne!(key, invalid.to_vec());
+        assert_ne!(b"hello".to_vec(), key);
+        assert!(InlineStr::from("") == Vec::<u8>::new());
+    }
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Counts allocations per thread, so tests can assert that code paths don't allocate.
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Returns the number of allocations `f` made on the current thread.
+    pub(crate) fn count_allocations(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    /// Small xorshift generator so the fuzz-style tests stay deterministic without extra dependencies.
+    pub(crate) struct XorShift(u64);
+
+    impl XorShift {
+        pub(crate) fn new(seed: u64) -> Self {
+            Self(seed | 1)
+        }
+
+        pub(crate) fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        pub(crate) fn string(&mut self, max_chars: usize) -> String {
+            self.string_from(&['a', 'b', 'z', ' ', '\n', 'é', 'ß', '€', '😀'], max_chars)
+        }
+
+        pub(crate) fn string_from(&mut self, alphabet: &[char], max_chars: usize) -> String {
+            let len = self.next() as usize % (max_chars + 1);
+            (0..len)
+                .map(|_| alphabet[self.next() as usize % alphabet.len()])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_eq_matches_str_eq() {
+        let mut rng = XorShift::new(0x5eed);
+
+        for _ in 0..2_000 {
+            let a = rng.string(100);
+            let b = if rng.next().is_multiple_of(2) {
+                a.clone()
+            } else {
+                rng.string(100)
+            };
+
+            let (inline_a, inline_b) = (InlineStr::from(&a), InlineStr::from(&b));
+            assert_eq!(inline_a == inline_b, a == b);
+        }
+    }
+
+    #[test]
+    fn test_eq_single_byte_difference() {
+        for len in [1, 7, 8, 31, 32, 33, 64, 100, 300] {
+            let a = "x".repeat(len);
+            let inline_a = InlineStr::from(&a);
+
+            for idx in [0, len / 2, len - 1] {
+                let mut b = a.clone().into_bytes();
+                b[idx] = b'y';
+                let b = String::from_utf8(b).unwrap();
+
+                assert_ne!(inline_a, InlineStr::from(b));
+            }
+
+            assert_eq!(inline_a, inline_a.clone());
+            assert_eq!(inline_a, InlineStr::from(a));
+        }
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::BuildHasher};
+
+use crate::InlineStr;
+
+/// Entry-style methods for maps keyed by [`InlineStr`] that take the key as a `&str`, and only
+/// convert it into an `InlineStr` when it has to be inserted.
+///
+/// Each method looks the key up before inserting, so misses probe the map twice.
+pub trait InlineStrMapExt<V> {
+    /// Returns the value for `key`, inserting the result of `default` if it's missing.
+    fn or_insert_with_str<F: FnOnce() -> V>(&mut self, key: &str, default: F) -> &mut V;
+
+    /// Calls `modify` on the value for `key`, or inserts the result of `default` if it's missing.
+    fn and_modify_or_insert_str<M, F>(&mut self, key: &str, modify: M, default: F)
+    where
+        M: FnOnce(&mut V),
+        F: FnOnce() -> V;
+}
+
+macro_rules! impl_map_ext {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<V, $($generics)*> InlineStrMapExt<V> for $ty {
+            fn or_insert_with_str<F: FnOnce() -> V>(&mut self, key: &str, default: F) -> &mut V {
+                if !self.contains_key(key) {
+                    self.insert(InlineStr::from(key), default());
+                }
+                self.get_mut(key).expect("key was just inserted")
+            }
+
+            fn and_modify_or_insert_str<M, F>(&mut self, key: &str, modify: M, default: F)
+            where
+                M: FnOnce(&mut V),
+                F: FnOnce() -> V,
+            {
+                match self.get_mut(key) {
+                    Some(value) => modify(value),
+                    None => {
+                        self.insert(InlineStr::from(key), default());
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_map_ext!([S: BuildHasher] HashMap<InlineStr, V, S>);
+impl_map_ext!([] BTreeMap<InlineStr, V>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::count_allocations;
+
+    const LONG: &str = "a counter key long enough to live on the heap";
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_map() {
+        let mut counts: HashMap<InlineStr, usize> = HashMap::with_capacity(4);
+
+        *counts.or_insert_with_str(LONG, || 0) += 1;
+        counts.and_modify_or_insert_str("short", |count| *count += 1, || 1);
+        assert_eq!(counts[LONG], 1);
+        assert_eq!(counts["short"], 1);
+
+        let allocations = count_allocations(|| {
+            *counts.or_insert_with_str(LONG, || 0) += 1;
+            counts.and_modify_or_insert_str(LONG, |count| *count += 1, || 1);
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(counts[LONG], 3);
+        assert_eq!(counts.len(), 2);
+
+        let allocations = count_allocations(|| {
+            counts.or_insert_with_str("another key long enough for the heap", || 0);
+        });
+        assert_eq!(allocations, 1);
+    }
+
+    #[test]
+    fn test_btree_map() {
+        let mut counts: BTreeMap<InlineStr, usize> = BTreeMap::new();
+
+        counts.and_modify_or_insert_str(LONG, |count| *count += 1, || 1);
+        *counts.or_insert_with_str("short", || 0) += 1;
+        assert_eq!(counts[LONG], 1);
+        assert_eq!(counts["short"], 1);
+
+        let allocations = count_allocations(|| {
+            counts.and_modify_or_insert_str(LONG, |count| *count += 1, || 1);
+            *counts.or_insert_with_str(LONG, || 0) += 1;
+        });
+        assert_eq!(allocations, 0);
+        assert_eq!(counts[LONG], 3);
+        assert_eq!(
+            counts.keys().collect::<Vec<_>>(),
+            [&InlineStr::from(LONG), &InlineStr::from("short")]
+        );
+    }
+}
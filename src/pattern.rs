@@ -0,0 +1,115 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+
+/// Patterns accepted by [`InlineStr`](crate::InlineStr)'s searching methods.
+///
+/// This mirrors the (unstable) [`core::str::pattern::Pattern`] trait, and is implemented for
+/// the same types: `char`, `&str`, `&String`, `[char; N]`, `&[char; N]`, `&[char]` and
+/// `FnMut(char) -> bool` closures. Matching is always delegated to the equivalent `str` method.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait Pattern: Sized + private::Sealed {
+    #[doc(hidden)]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str>;
+
+    #[doc(hidden)]
+    fn strip_suffix_of(self, haystack: &str) -> Option<&str>;
+
+    #[doc(hidden)]
+    fn split_once_of(self, haystack: &str) -> Option<(&str, &str)>;
+
+    #[doc(hidden)]
+    fn rsplit_once_of(self, haystack: &str) -> Option<(&str, &str)>;
+
+    #[doc(hidden)]
+    fn match_indices_of<'a>(self, haystack: &'a str) -> impl Iterator<Item = (usize, &'a str)> + 'a
+    where
+        Self: 'a;
+
+    #[doc(hidden)]
+    fn splitn_of<'a>(self, n: usize, haystack: &'a str) -> impl Iterator<Item = &'a str> + 'a
+    where
+        Self: 'a;
+
+    #[doc(hidden)]
+    fn rsplit_of<'a>(self, haystack: &'a str) -> impl Iterator<Item = &'a str> + 'a
+    where
+        Self: 'a;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_pattern {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> private::Sealed for $ty $(where $($bounds)*)? {}
+
+        impl<$($generics)*> Pattern for $ty $(where $($bounds)*)? {
+            fn strip_prefix_of(self, haystack: &str) -> Option<&str> {
+                haystack.strip_prefix(self)
+            }
+
+            fn strip_suffix_of(self, haystack: &str) -> Option<&str> {
+                haystack.strip_suffix(self)
+            }
+
+            fn split_once_of(self, haystack: &str) -> Option<(&str, &str)> {
+                haystack.split_once(self)
+            }
+
+            fn rsplit_once_of(self, haystack: &str) -> Option<(&str, &str)> {
+                haystack.rsplit_once(self)
+            }
+
+            fn match_indices_of<'a>(
+                self,
+                haystack: &'a str,
+            ) -> impl Iterator<Item = (usize, &'a str)> + 'a
+            where
+                Self: 'a,
+            {
+                haystack.match_indices(self)
+            }
+
+            fn splitn_of<'a>(
+                self,
+                n: usize,
+                haystack: &'a str,
+            ) -> impl Iterator<Item = &'a str> + 'a
+            where
+                Self: 'a,
+            {
+                haystack.splitn(n, self)
+            }
+
+            fn rsplit_of<'a>(self, haystack: &'a str) -> impl Iterator<Item = &'a str> + 'a
+            where
+                Self: 'a,
+            {
+                haystack.rsplit(self)
+            }
+        }
+    };
+}
+
+impl_pattern!([] char);
+impl_pattern!(['b] &'b str);
+impl_pattern!(['b] &'b String);
+impl_pattern!(['b] &'b [char]);
+impl_pattern!([const N: usize] [char; N]);
+impl_pattern!(['b, const N: usize] &'b [char; N]);
+impl_pattern!([F] F where F: FnMut(char) -> bool);
@@ -0,0 +1,56 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`proptest`](::proptest) strategies for [`InlineStr`].
+
+use ::proptest::{prop_oneof, strategy::Strategy};
+
+use crate::InlineStr;
+
+/// A strategy producing arbitrary [`InlineStr`]s, mixing short strings that are stored inline
+/// with longer ones that spill to the heap.
+pub fn any_inline_str() -> impl Strategy<Value = InlineStr> {
+    prop_oneof![short_inline_str(), "\\PC{8,64}".prop_map(InlineStr::from)]
+}
+
+/// Strings of up to [`InlineStr::INLINE_CAPACITY`] bytes. The regex quantifier counts chars, so
+/// the generated string is cut back to the last char boundary that fits.
+fn short_inline_str() -> impl Strategy<Value = InlineStr> {
+    "\\PC{0,7}".prop_map(|s| {
+        let mut end = s.len().min(InlineStr::INLINE_CAPACITY);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        InlineStr::from(&s[..end])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_len_matches_str(s in any_inline_str()) {
+            prop_assert_eq!(s.len(), s.as_str().len());
+        }
+
+        #[test]
+        fn test_short_strings_are_inline(s in short_inline_str()) {
+            prop_assert!(s.is_inline());
+        }
+    }
+}
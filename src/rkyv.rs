@@ -0,0 +1,89 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `InlineStr` archives to the same [`ArchivedString`] as `String`, so the inline/heap
+//! distinction is not preserved and archived data stays interchangeable between the two types.
+
+use rkyv::{
+    rancor::{Fallible, Source},
+    string::{ArchivedString, StringResolver},
+    Archive, Deserialize, DeserializeUnsized, Place, Serialize, SerializeUnsized,
+};
+
+use crate::InlineStr;
+
+impl Archive for InlineStr {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self, resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for InlineStr
+where
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<InlineStr, D> for ArchivedString
+where
+    str: DeserializeUnsized<str, D>,
+{
+    fn deserialize(&self, _: &mut D) -> Result<InlineStr, D::Error> {
+        Ok(InlineStr::from(self.as_str()))
+    }
+}
+
+impl PartialEq<InlineStr> for ArchivedString {
+    fn eq(&self, other: &InlineStr) -> bool {
+        self.as_str().eq(&**other)
+    }
+}
+
+impl PartialEq<ArchivedString> for InlineStr {
+    fn eq(&self, other: &ArchivedString) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+
+    use super::*;
+
+    #[test]
+    fn test_archive_roundtrip() {
+        for text in ["short", "a much longer string that is stored on the heap"] {
+            let value = InlineStr::from(text);
+
+            let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+            let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+            assert_eq!(archived.as_str(), text);
+            assert_eq!(*archived, value);
+
+            let deserialized: InlineStr = rkyv::deserialize::<InlineStr, Error>(archived).unwrap();
+            assert_eq!(deserialized, value);
+
+            let as_string = rkyv::to_bytes::<Error>(&text.to_string()).unwrap();
+            assert_eq!(bytes.as_slice(), as_string.as_slice());
+        }
+    }
+}
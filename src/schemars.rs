@@ -0,0 +1,90 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`JsonSchema`] for [`InlineStr`], producing the same schema as `String`.
+
+use alloc::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use crate::InlineStr;
+
+/// Uses the same schema as `str` and `String`, which is always inlined, so no separate definition
+/// is generated for `InlineStr`.
+impl JsonSchema for InlineStr {
+    fn inline_schema() -> bool {
+        <str as JsonSchema>::inline_schema()
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <str as JsonSchema>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <str as JsonSchema>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <str as JsonSchema>::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::String, vec::Vec};
+    use std::collections::HashMap;
+
+    use schemars::schema_for;
+
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    #[schemars(rename = "Record")]
+    struct WithInlineStr {
+        name: InlineStr,
+        nickname: Option<InlineStr>,
+        tags: Vec<InlineStr>,
+        counts: HashMap<InlineStr, u32>,
+        sorted: BTreeMap<InlineStr, Option<InlineStr>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    #[schemars(rename = "Record")]
+    struct WithString {
+        name: String,
+        nickname: Option<String>,
+        tags: Vec<String>,
+        counts: HashMap<String, u32>,
+        sorted: BTreeMap<String, Option<String>>,
+    }
+
+    #[test]
+    fn test_same_schema_as_string() {
+        assert_eq!(schema_for!(InlineStr), schema_for!(String));
+        assert_eq!(InlineStr::schema_id(), String::schema_id());
+
+        let schema = schema_for!(WithInlineStr);
+        assert_eq!(schema, schema_for!(WithString));
+        assert!(schema.get("$defs").is_none());
+        assert_eq!(
+            schema.get("properties").unwrap()["counts"],
+            serde_json::json!({
+                "type": "object",
+                "additionalProperties": { "type": "integer", "format": "uint32", "minimum": 0 }
+            })
+        );
+    }
+}
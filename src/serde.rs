@@ -0,0 +1,283 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `Serialize` and `Deserialize` for [`InlineStr`]. Values are serialized as plain strings, and
+//! deserialized from borrowed strings, owned strings, or byte strings holding valid UTF-8.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use serde::{
+    de::{
+        self,
+        value::{StrDeserializer, StringDeserializer},
+        IntoDeserializer, Visitor,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::InlineStr;
+
+impl Serialize for InlineStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+/// Values are always serialized as strings, but deserializing also accepts byte strings holding
+/// valid UTF-8, for binary formats that hand them to string deserialization, like MessagePack.
+impl<'de> Deserialize<'de> for InlineStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(InlineStrVisitor)
+    }
+}
+
+/// Builds the string straight from whatever the deserializer hands over, so short strings
+/// borrowed from the input never go through an intermediate `String`.
+struct InlineStrVisitor;
+
+impl Visitor<'_> for InlineStrVisitor {
+    type Value = InlineStr;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(InlineStr::from(value))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(InlineStr::from(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        crate::from_utf8(value)
+            .map(InlineStr::from)
+            .map_err(|err| E::custom(format_args!("invalid UTF-8 in byte string: {err}")))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        self.visit_bytes(&value)
+    }
+}
+
+/// Deserializes values from the string, like `String::into_deserializer`.
+impl<E: de::Error> IntoDeserializer<'_, E> for InlineStr {
+    type Deserializer = StringDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        String::from(self.as_str()).into_deserializer()
+    }
+}
+
+impl<'a, E: de::Error> IntoDeserializer<'_, E> for &'a InlineStr {
+    type Deserializer = StrDeserializer<'a, E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self.as_str().into_deserializer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+
+    use serde::de::value::Error as ValueError;
+
+    use super::*;
+    use crate::tests::count_allocations;
+
+    const TEXTS: [&str; 4] = [
+        "",
+        "short",
+        "héllo \"wörld\"",
+        "a much longer string that is stored on the heap",
+    ];
+
+    #[test]
+    fn test_json_roundtrip() {
+        for text in TEXTS {
+            let value = InlineStr::from(text);
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, serde_json::to_string(text).unwrap());
+
+            assert_eq!(serde_json::from_str::<InlineStr>(&json).unwrap(), text);
+            assert_eq!(
+                serde_json::from_reader::<_, InlineStr>(json.as_bytes()).unwrap(),
+                text
+            );
+
+            let json_value = serde_json::to_value(&value).unwrap();
+            assert_eq!(json_value, serde_json::Value::from(text));
+            assert_eq!(InlineStr::deserialize(&json_value).unwrap(), text);
+            assert_eq!(
+                serde_json::from_value::<InlineStr>(json_value).unwrap(),
+                text
+            );
+        }
+
+        let err = serde_json::from_str::<InlineStr>("42").unwrap_err();
+        assert!(err.to_string().contains("expected a string"));
+    }
+
+    #[test]
+    fn test_transparent_newtype() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        #[serde(transparent)]
+        struct Key(InlineStr);
+
+        for text in TEXTS {
+            let key = Key(InlineStr::from(text));
+            let json = serde_json::to_string(&key).unwrap();
+            assert_eq!(json, serde_json::to_string(text).unwrap());
+            assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key);
+        }
+
+        // Strings can also be used as JSON object keys
+        let map = BTreeMap::from([(Key(InlineStr::from("id")), 1)]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"id":1}"#);
+        assert_eq!(
+            serde_json::from_str::<BTreeMap<Key, u32>>(&json).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn test_into_deserializer() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Level {
+            Debug,
+            Info,
+        }
+
+        // Like with `String`, plain strings only deserialize into transparent newtypes
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(transparent)]
+        struct Name(String);
+
+        let level = InlineStr::from("info");
+        assert_eq!(
+            Level::deserialize(IntoDeserializer::<ValueError>::into_deserializer(&level)),
+            Ok(Level::Info)
+        );
+        assert_eq!(
+            Level::deserialize(IntoDeserializer::<ValueError>::into_deserializer(
+                InlineStr::from("debug")
+            )),
+            Ok(Level::Debug)
+        );
+
+        let name = InlineStr::from("a much longer name on the heap");
+        assert_eq!(
+            Name::deserialize(IntoDeserializer::<ValueError>::into_deserializer(&name)),
+            Ok(Name(name.to_string()))
+        );
+
+        let err = bool::deserialize(IntoDeserializer::<ValueError>::into_deserializer(
+            InlineStr::from("true"),
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: string \"true\", expected a boolean"
+        );
+    }
+
+    #[test]
+    fn test_bincode_roundtrip() {
+        let config = bincode::config::standard();
+        for text in TEXTS {
+            let value = InlineStr::from(text);
+            let bytes = bincode::serde::encode_to_vec(&value, config).unwrap();
+            assert_eq!(
+                bytes,
+                bincode::serde::encode_to_vec(text.to_string(), config).unwrap()
+            );
+
+            let (decoded, _): (InlineStr, _) =
+                bincode::serde::decode_from_slice(&bytes, config).unwrap();
+            assert_eq!(decoded, text);
+        }
+    }
+
+    #[test]
+    fn test_binary_formats() {
+        for text in TEXTS {
+            let value = InlineStr::from(text);
+
+            let msgpack = rmp_serde::to_vec(&value).unwrap();
+            assert_eq!(rmp_serde::from_slice::<InlineStr>(&msgpack).unwrap(), text);
+            let msgpack_bin = rmp_serde::to_vec(&BytesOf(text.as_bytes())).unwrap();
+            assert_eq!(
+                rmp_serde::from_slice::<InlineStr>(&msgpack_bin).unwrap(),
+                text
+            );
+
+            let mut cbor = Vec::new();
+            ciborium::into_writer(&value, &mut cbor).unwrap();
+            assert_eq!(
+                ciborium::from_reader::<InlineStr, _>(&cbor[..]).unwrap(),
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8_bytes() {
+        let msgpack = rmp_serde::to_vec(&BytesOf(b"ab\xff")).unwrap();
+        let err = rmp_serde::from_slice::<InlineStr>(&msgpack).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid UTF-8 in byte string: invalid utf-8 sequence of 1 bytes from index 2"
+        );
+    }
+
+    /// Serializes a byte slice as a byte string, the way binary formats encode raw bytes.
+    struct BytesOf<'a>(&'a [u8]);
+
+    impl Serialize for BytesOf<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    #[test]
+    fn test_short_keys_dont_allocate() {
+        let json = r#"["id", "name", "key"]"#;
+        let mut keys = None;
+        assert_eq!(
+            count_allocations(|| {
+                keys = Some(serde_json::from_str::<[InlineStr; 3]>(json).unwrap());
+            }),
+            0
+        );
+        assert_eq!(keys.unwrap(), ["id", "name", "key"]);
+
+        // Only the long string's own buffer is allocated
+        let mut long = None;
+        assert_eq!(
+            count_allocations(|| {
+                long = Some(serde_json::from_str::<InlineStr>(r#""a much longer key""#).unwrap());
+            }),
+            1
+        );
+        assert!(long.unwrap().is_heap_allocated());
+
+        let owned: Vec<InlineStr> = serde_json::from_str(json).unwrap();
+        assert_eq!(owned, ["id", "name", "key"]);
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for `#[serde(deserialize_with = "...")]` on [`InlineStr`] fields.
+//!
+//! ```
+//! use inline_str::InlineStr;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     #[serde(default, deserialize_with = "inline_str::serde_helpers::empty_as_none")]
+//!     name: Option<InlineStr>,
+//!     #[serde(deserialize_with = "inline_str::serde_helpers::trimmed")]
+//!     region: InlineStr,
+//!     #[serde(deserialize_with = "inline_str::serde_helpers::from_display")]
+//!     version: InlineStr,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{"name": "", "region": " eu ", "version": 2}"#)?;
+//! assert_eq!(config.name, None);
+//! assert_eq!(config.region, "eu");
+//! assert_eq!(config.version, "2");
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! `deserialize_with` is only called for fields that are present, add `#[serde(default)]` to the
+//! `Option` variants so that missing fields become `None` too.
+
+use core::fmt::{self, Display, Write};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{builder::Builder, InlineStr};
+
+/// Deserializes an optional string, treating the empty string like `null`.
+pub fn empty_as_none<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<InlineStr>, D::Error> {
+    Ok(Option::<InlineStr>::deserialize(deserializer)?.filter(|value| !value.is_empty()))
+}
+
+/// Deserializes a string with leading and trailing whitespace removed.
+pub fn trimmed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<InlineStr, D::Error> {
+    deserializer.deserialize_str(TrimmedVisitor)
+}
+
+/// Like [`trimmed`], for an optional string. Whitespace-only strings become `Some("")`, combine
+/// with [`Option::filter`] in your own helper to treat them as missing.
+pub fn trimmed_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<InlineStr>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(transparent)]
+    struct Trimmed(#[serde(deserialize_with = "trimmed")] InlineStr);
+
+    Ok(Option::<Trimmed>::deserialize(deserializer)?.map(|value| value.0))
+}
+
+/// Deserializes a string, or a number, bool or char formatted with its `Display` impl.
+///
+/// This needs a self-describing format, like JSON.
+pub fn from_display<'de, D: Deserializer<'de>>(deserializer: D) -> Result<InlineStr, D::Error> {
+    deserializer.deserialize_any(FromDisplayVisitor)
+}
+
+/// Like [`from_display`], for an optional value.
+pub fn from_display_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<InlineStr>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(transparent)]
+    struct FromDisplay(#[serde(deserialize_with = "from_display")] InlineStr);
+
+    Ok(Option::<FromDisplay>::deserialize(deserializer)?.map(|value| value.0))
+}
+
+struct TrimmedVisitor;
+
+impl Visitor<'_> for TrimmedVisitor {
+    type Value = InlineStr;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(InlineStr::from(value.trim()))
+    }
+}
+
+struct FromDisplayVisitor;
+
+impl FromDisplayVisitor {
+    fn format<E: de::Error>(value: impl Display) -> Result<InlineStr, E> {
+        let mut builder = Builder::new();
+        write!(builder, "{value}").map_err(E::custom)?;
+        Ok(builder.finish())
+    }
+}
+
+impl Visitor<'_> for FromDisplayVisitor {
+    type Value = InlineStr;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string, number, bool or char")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_char<E: de::Error>(self, value: char) -> Result<Self::Value, E> {
+        Self::format(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(InlineStr::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Record {
+        #[serde(default, deserialize_with = "empty_as_none")]
+        name: Option<InlineStr>,
+        #[serde(default = "empty", deserialize_with = "trimmed")]
+        region: InlineStr,
+        #[serde(default, deserialize_with = "trimmed_option")]
+        zone: Option<InlineStr>,
+        #[serde(default = "empty", deserialize_with = "from_display")]
+        version: InlineStr,
+        #[serde(default, deserialize_with = "from_display_option")]
+        build: Option<InlineStr>,
+    }
+
+    fn empty() -> InlineStr {
+        InlineStr::EMPTY
+    }
+
+    fn parse(json: &str) -> Record {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_empty_as_none() {
+        assert_eq!(parse("{}").name, None);
+        assert_eq!(parse(r#"{"name": null}"#).name, None);
+        assert_eq!(parse(r#"{"name": ""}"#).name, None);
+        assert_eq!(parse(r#"{"name": " "}"#).name.unwrap(), " ");
+        assert_eq!(parse(r#"{"name": "x"}"#).name.unwrap(), "x");
+    }
+
+    #[test]
+    fn test_trimmed() {
+        assert_eq!(parse(r#"{"region": "  eu-west \n"}"#).region, "eu-west");
+        assert_eq!(parse(r#"{"region": "   "}"#).region, "");
+        assert_eq!(
+            parse(r#"{"region": "\t a much longer region name on the heap "}"#).region,
+            "a much longer region name on the heap"
+        );
+        assert!(serde_json::from_str::<Record>(r#"{"region": 1}"#).is_err());
+
+        assert_eq!(parse("{}").zone, None);
+        assert_eq!(parse(r#"{"zone": null}"#).zone, None);
+        assert_eq!(parse(r#"{"zone": " a "}"#).zone.unwrap(), "a");
+        assert_eq!(parse(r#"{"zone": "  "}"#).zone.unwrap(), "");
+    }
+
+    #[test]
+    fn test_from_display() {
+        assert_eq!(parse(r#"{"version": 42}"#).version, "42");
+        assert_eq!(parse(r#"{"version": -7}"#).version, "-7");
+        assert_eq!(parse(r#"{"version": 1.5}"#).version, "1.5");
+        assert_eq!(parse(r#"{"version": true}"#).version, "true");
+        assert_eq!(parse(r#"{"version": "v1"}"#).version, "v1");
+        assert_eq!(
+            parse(r#"{"version": 18446744073709551615}"#).version,
+            u64::MAX.to_string()
+        );
+        assert!(serde_json::from_str::<Record>(r#"{"version": [1]}"#).is_err());
+
+        assert_eq!(parse("{}").build, None);
+        assert_eq!(parse(r#"{"build": null}"#).build, None);
+        assert_eq!(parse(r#"{"build": 7}"#).build.unwrap(), "7");
+    }
+}
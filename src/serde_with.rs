@@ -0,0 +1,99 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `serde_with` adapters, so [`InlineStr`] can be used in `#[serde_as]` attributes, on its own or
+//! inside adapters like `Vec<InlineStr>` and `BTreeMap<InlineStr, _>`.
+
+use core::borrow::Borrow;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::InlineStr;
+
+/// Serializes any string type as a plain string, like [`InlineStr`] itself, so
+/// `#[serde_as(as = "InlineStr")]` can be used on fields and inside adapters like
+/// `Vec<InlineStr>` or `HashMap<InlineStr, _>`.
+impl<T: Borrow<str>> SerializeAs<T> for InlineStr {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        source.borrow().serialize(serializer)
+    }
+}
+
+/// Deserializes an [`InlineStr`], and converts it into any type that can be built from one.
+impl<'de, T: From<InlineStr>> DeserializeAs<'de, T> for InlineStr {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        InlineStr::deserialize(deserializer).map(T::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+    use serde_with::{serde_as, DisplayFromStr, NoneAsEmptyString, VecSkipError};
+
+    use super::*;
+    use crate::CharCounted;
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        #[serde_as(as = "NoneAsEmptyString")]
+        name: Option<InlineStr>,
+        #[serde_as(as = "InlineStr")]
+        label: CharCounted,
+        #[serde_as(as = "VecSkipError<_>")]
+        tags: Vec<InlineStr>,
+        #[serde_as(as = "BTreeMap<InlineStr, DisplayFromStr>")]
+        counts: BTreeMap<InlineStr, u32>,
+    }
+
+    fn record(name: Option<&str>) -> Record {
+        Record {
+            name: name.map(InlineStr::from),
+            label: CharCounted::from("日本語"),
+            tags: vec![InlineStr::from("a"), InlineStr::from("a much longer tag")],
+            counts: BTreeMap::from([(InlineStr::from("id"), 1), (InlineStr::from("key"), 2)]),
+        }
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        for value in [record(Some("short")), record(None)] {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), value);
+        }
+
+        let json = serde_json::to_string(&record(None)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"","label":"日本語","tags":["a","a much longer tag"],"counts":{"id":"1","key":"2"}}"#
+        );
+
+        let skipped: Record =
+            serde_json::from_str(r#"{"name":"x","label":"l","tags":["a",1,"b"],"counts":{}}"#)
+                .unwrap();
+        assert_eq!(skipped.tags, ["a", "b"]);
+        assert_eq!(skipped.label.char_len(), 1);
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        for value in [record(Some("short")), record(None)] {
+            let toml: String = toml::to_string(&value).unwrap();
+            assert_eq!(toml::from_str::<Record>(&toml).unwrap(), value);
+        }
+    }
+}
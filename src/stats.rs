@@ -0,0 +1,69 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Process-wide construction counters, enabled by the `stats` feature.
+//!
+//! Every new buffer is recorded as it is created, with relaxed atomics. Clones share their buffer
+//! and aren't counted.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::InlineStr;
+
+static INLINE_CONSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+static HEAP_CONSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+static HEAP_BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// A snapshot of the process-wide construction counters, see [`stats`].
+///
+/// Only constructions that create a new buffer are counted, cloning a heap-allocated string shares
+/// its buffer and isn't.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of strings that were stored inline.
+    pub inline_constructions: u64,
+    /// The number of strings that allocated a heap buffer.
+    pub heap_constructions: u64,
+    /// The total size of all heap buffers allocated, as reported by [`InlineStr::heap_size`].
+    pub heap_bytes_allocated: u64,
+}
+
+/// Returns the current values of the construction counters.
+///
+/// The counters are updated with relaxed atomics, so a snapshot taken while other threads are
+/// constructing strings may not be consistent across fields.
+pub fn stats() -> Stats {
+    Stats {
+        inline_constructions: INLINE_CONSTRUCTIONS.load(Ordering::Relaxed),
+        heap_constructions: HEAP_CONSTRUCTIONS.load(Ordering::Relaxed),
+        heap_bytes_allocated: HEAP_BYTES_ALLOCATED.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets all construction counters to zero.
+pub fn reset_stats() {
+    INLINE_CONSTRUCTIONS.store(0, Ordering::Relaxed);
+    HEAP_CONSTRUCTIONS.store(0, Ordering::Relaxed);
+    HEAP_BYTES_ALLOCATED.store(0, Ordering::Relaxed);
+}
+
+pub(crate) fn record(len: usize) {
+    if InlineStr::will_inline(len) {
+        INLINE_CONSTRUCTIONS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        HEAP_CONSTRUCTIONS.fetch_add(1, Ordering::Relaxed);
+        HEAP_BYTES_ALLOCATED.fetch_add(crate::heap_size_of(len) as u64, Ordering::Relaxed);
+    }
+}
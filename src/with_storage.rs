@@ -0,0 +1,80 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::InlineStr;
+
+/// Serializes the wrapped [`InlineStr`] as an `(is_inline, string)` pair, recording how it was
+/// stored, for snapshots and debugging output.
+///
+/// Storage is decided by the string's length alone, so a deserialized value is stored the same way
+/// the serialized one was, as long as both sides agree on [`InlineStr::INLINE_CAPACITY`]. The flag
+/// is informational, and a value whose flag doesn't match its length (for example one written by
+/// a build with a different capacity) is stored the way its length dictates.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WithStorage(pub InlineStr);
+
+impl Serialize for WithStorage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0.is_inline(), self.0.as_str()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for WithStorage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (_, value) = <(bool, InlineStr)>::deserialize(deserializer)?;
+        Ok(WithStorage(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use bincode::config;
+
+    use super::*;
+
+    #[test]
+    fn test_with_storage_roundtrip() {
+        for text in [
+            "",
+            "short",
+            "a much longer string that is stored on the heap",
+        ] {
+            let value = WithStorage(InlineStr::from(text));
+            let inline = value.0.is_inline();
+
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, format!("[{inline},\"{text}\"]"));
+            let decoded: WithStorage = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.0.is_inline(), inline);
+
+            let bytes = bincode::serde::encode_to_vec(&value, config::standard()).unwrap();
+            let (decoded, _): (WithStorage, _) =
+                bincode::serde::decode_from_slice(&bytes, config::standard()).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.0.is_inline(), inline);
+        }
+    }
+
+    #[test]
+    fn test_with_storage_mismatched_flag() {
+        let decoded: WithStorage = serde_json::from_str("[false,\"abc\"]").unwrap();
+        assert_eq!(decoded.0, "abc");
+        assert!(decoded.0.is_inline());
+    }
+}
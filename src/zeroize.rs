@@ -0,0 +1,189 @@
+// Copyright 2024 Adam Gutglick
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// 	http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zeroizing a plain [`InlineStr`] can only wipe inline bytes, since a heap buffer may be shared
+//! with other clones. [`SecretInlineStr`] owns a buffer that is never shared, so it can always
+//! wipe it.
+
+use core::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::InlineStr;
+
+/// Wipes inline strings in place and resets the value to [`InlineStr::EMPTY`].
+///
+/// A heap buffer might be shared with other clones, and this crate can't tell whether it is, so
+/// it is released without being wiped. Use [`SecretInlineStr`] for values that must be wiped.
+impl Zeroize for InlineStr {
+    fn zeroize(&mut self) {
+        if self.is_inline() {
+            self.inner.make_mut().zeroize();
+        }
+        *self = InlineStr::EMPTY;
+    }
+}
+
+/// A string for secrets, which wipes its bytes when dropped and redacts its `Debug` and
+/// `Display` output.
+///
+/// The bytes are always copied into a buffer that is never shared, including when cloning, so
+/// they can be wiped wherever they are stored. Strings it was created from are left as they were.
+/// It doesn't implement serde's traits, so secrets can't be serialized by accident.
+pub struct SecretInlineStr(InlineStr);
+
+impl SecretInlineStr {
+    /// Copies `secret` into a new, unshared buffer.
+    pub fn new(secret: &str) -> Self {
+        // `From<&str>` always copies heap-sized strings into a fresh allocation
+        Self(InlineStr::from(secret))
+    }
+
+    /// Returns the secret.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Wipes the bytes in place, leaving a string of NUL bytes of the same length.
+    fn wipe(&mut self) {
+        // The buffer is never shared, so this doesn't copy it
+        self.0.inner.make_mut().zeroize();
+    }
+}
+
+impl Clone for SecretInlineStr {
+    fn clone(&self) -> Self {
+        Self::new(self.expose_secret())
+    }
+}
+
+impl Zeroize for SecretInlineStr {
+    fn zeroize(&mut self) {
+        self.wipe();
+        self.0 = InlineStr::EMPTY;
+    }
+}
+
+impl Drop for SecretInlineStr {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl ZeroizeOnDrop for SecretInlineStr {}
+
+impl fmt::Debug for SecretInlineStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl fmt::Display for SecretInlineStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "sk-a-long-api-token-that-lives-on-the-heap";
+
+    #[test]
+    fn test_zeroize_inline_str() {
+        let mut inline = InlineStr::from("pass");
+        inline.zeroize();
+        assert_eq!(inline, "");
+
+        let mut heap = InlineStr::from(TOKEN);
+        let shared = heap.clone();
+        heap.zeroize();
+        assert_eq!(heap, "");
+        assert_eq!(shared, TOKEN);
+    }
+
+    #[test]
+    fn test_secret_wiped_in_place() {
+        for text in ["pass", TOKEN] {
+            let mut secret = SecretInlineStr::new(text);
+            let ptr = secret.expose_secret().as_ptr();
+
+            secret.wipe();
+            assert_eq!(secret.expose_secret().as_ptr(), ptr);
+            assert_eq!(secret.expose_secret().len(), text.len());
+            assert!(secret.expose_secret().bytes().all(|b| b == 0));
+        }
+    }
+
+    #[test]
+    fn test_secret_never_shared() {
+        let source = InlineStr::from(TOKEN);
+        let secret = SecretInlineStr::new(&source);
+        let clone = secret.clone();
+
+        assert_ne!(secret.expose_secret().as_ptr(), source.as_ptr());
+        assert_ne!(
+            clone.expose_secret().as_ptr(),
+            secret.expose_secret().as_ptr()
+        );
+
+        drop(secret);
+        assert_eq!(clone.expose_secret(), TOKEN);
+        assert_eq!(source, TOKEN);
+
+        let mut zeroized = clone.clone();
+        zeroized.zeroize();
+        assert_eq!(zeroized.expose_secret(), "");
+        assert_eq!(clone.expose_secret(), TOKEN);
+    }
+
+    #[test]
+    fn test_secret_redacted() {
+        let secret = SecretInlineStr::new(TOKEN);
+        assert_eq!(format!("{secret:?}"), "[REDACTED]");
+        assert_eq!(format!("{secret}"), "[REDACTED]");
+        assert_eq!(format!("{secret:#?} {secret:>20}"), "[REDACTED] [REDACTED]");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_plain_only() {
+        use core::marker::PhantomData;
+
+        // Inherent methods win over trait methods, but only apply if `T: Serialize`
+        struct Probe<T>(PhantomData<T>);
+        impl<T: serde::Serialize> Probe<T> {
+            fn is_serialize(&self) -> bool {
+                true
+            }
+        }
+        trait Fallback {
+            fn is_serialize(&self) -> bool {
+                false
+            }
+        }
+        impl<T> Fallback for Probe<T> {}
+
+        assert!(Probe::<InlineStr>(PhantomData).is_serialize());
+        assert!(!Probe::<SecretInlineStr>(PhantomData).is_serialize());
+
+        let mut value = InlineStr::from(TOKEN);
+        value.zeroize();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            serde_json::to_string(&InlineStr::EMPTY).unwrap()
+        );
+    }
+}
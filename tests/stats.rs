@@ -0,0 +1,59 @@
+#![cfg(feature = "stats")]
+
+use std::{sync::Mutex, thread};
+
+use inline_str::{reset_stats, stats, InlineStr};
+
+// The counters are process-wide, so tests touching them can't run concurrently
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_counts_constructions() {
+    let _guard = LOCK.lock().unwrap();
+    reset_stats();
+
+    let short = InlineStr::from("short");
+    let small = InlineStr::from("x".repeat(100));
+    let big = InlineStr::from(&"y".repeat(1000));
+    let _clone = big.clone();
+
+    let stats = stats();
+    assert_eq!(stats.inline_constructions, 1);
+    assert_eq!(stats.heap_constructions, 2);
+    assert_eq!(
+        stats.heap_bytes_allocated,
+        (short.heap_size() + small.heap_size() + big.heap_size()) as u64
+    );
+    assert_eq!(stats.heap_bytes_allocated, 102 + 1008);
+
+    reset_stats();
+    assert_eq!(inline_str::stats(), Default::default());
+}
+
+#[test]
+fn test_counts_across_threads() {
+    let _guard = LOCK.lock().unwrap();
+    reset_stats();
+
+    const THREADS: u64 = 8;
+    const PER_THREAD: u64 = 1000;
+
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for idx in 0..PER_THREAD {
+                    let len = if idx % 2 == 0 { 3 } else { 30 };
+                    let _ = InlineStr::from("z".repeat(len));
+                }
+            });
+        }
+    });
+
+    let stats = stats();
+    assert_eq!(stats.inline_constructions, THREADS * PER_THREAD / 2);
+    assert_eq!(stats.heap_constructions, THREADS * PER_THREAD / 2);
+    assert_eq!(
+        stats.heap_bytes_allocated,
+        THREADS * PER_THREAD / 2 * InlineStr::from("z".repeat(30)).heap_size() as u64
+    );
+}